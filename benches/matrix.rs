@@ -0,0 +1,53 @@
+// Matrix4 products and batch transforms. The SIMD path is chosen at compile time, so
+// compare paths by running with different flags, e.g.
+//   cargo bench
+//   RUSTFLAGS="-C target-feature=+avx" cargo bench
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use opengl::math::{Matrix4, Rad, Vector3, Vector4};
+
+fn sample_matrix() -> Matrix4 {
+    Matrix4::perspective(Rad(1.1), 1.6, 0.1, 250.0)
+        * Matrix4::look_at_rh(
+            Vector3::new(4.0, 3.0, 5.0),
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )
+}
+
+fn matrix_benches(c: &mut Criterion) {
+    let a = sample_matrix();
+    let b = Matrix4::create_translation(Matrix4::identity(), Vector3::new(1.0, 2.0, 3.0));
+
+    c.bench_function("matrix4 * matrix4", |bench| {
+        bench.iter(|| black_box(a) * black_box(b))
+    });
+
+    c.bench_function("matrix4 multiply_matrix", |bench| {
+        bench.iter(|| {
+            let mut m = black_box(a);
+            m.multiply_matrix(black_box(b));
+            m
+        })
+    });
+
+    let v = Vector4::new(1.0, 2.0, 3.0, 1.0);
+    c.bench_function("matrix4 * vector4", |bench| {
+        bench.iter(|| black_box(a) * black_box(v))
+    });
+
+    // Roughly one frame worth of animated instances
+    let mut points: Vec<Vector3> = (0..10_000)
+        .map(|i| Vector3::new(i as f32, (i % 7) as f32, (i % 13) as f32))
+        .collect();
+    c.bench_function("transform_points 10k", |bench| {
+        bench.iter(|| b.transform_points(black_box(&mut points)))
+    });
+
+    let mut vectors: Vec<Vector4> = points.iter().map(|p| p.extend(1.0)).collect();
+    c.bench_function("transform_vectors 10k", |bench| {
+        bench.iter(|| b.transform_vectors(black_box(&mut vectors)))
+    });
+}
+
+criterion_group!(benches, matrix_benches);
+criterion_main!(benches);
@@ -1,6 +1,7 @@
 use gl::types::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BufferType {
     // Array Buffers holds arrays of vertex data for drawing.
     Array = gl::ARRAY_BUFFER as isize, //VBO
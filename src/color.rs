@@ -0,0 +1,408 @@
+// Colors for clear_color, uniforms and textures.
+// Color holds linear RGB with straight (not premultiplied) alpha, so lighting and blending
+// math on it is correct. Rgba8 holds 8-bit sRGB encoded values, like image files,
+// hex codes and color pickers use.
+use crate::math::{Vector3, Vector4};
+
+// Serialized as [r, g, b, a], like a Vector4
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Vector4", into = "Vector4")
+)]
+#[repr(C)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Rgba8 {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+// sRGB transfer functions for a single channel in [0, 1], alpha is never encoded
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+impl Color {
+    pub const TRANSPARENT: Color = Color::new(0.0, 0.0, 0.0, 0.0);
+    pub const BLACK: Color = Color::new(0.0, 0.0, 0.0, 1.0);
+    pub const WHITE: Color = Color::new(1.0, 1.0, 1.0, 1.0);
+    pub const RED: Color = Color::new(1.0, 0.0, 0.0, 1.0);
+    pub const GREEN: Color = Color::new(0.0, 1.0, 0.0, 1.0);
+    pub const BLUE: Color = Color::new(0.0, 0.0, 1.0, 1.0);
+
+    // @params: r, g and b are linear
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    // Opaque, r, g and b are linear
+    pub const fn rgb(r: f32, g: f32, b: f32) -> Color {
+        Color::new(r, g, b, 1.0)
+    }
+
+    // @params: r, g and b are sRGB encoded, e.g. values picked in an image editor
+    pub fn from_srgb(r: f32, g: f32, b: f32, a: f32) -> Color {
+        Color::new(srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a)
+    }
+
+    // sRGB encoded r, g, b and the unchanged alpha
+    pub fn to_srgb(self) -> [f32; 4] {
+        [
+            linear_to_srgb(self.r),
+            linear_to_srgb(self.g),
+            linear_to_srgb(self.b),
+            self.a,
+        ]
+    }
+
+    // "#rgb", "#rgba", "#rrggbb" or "#rrggbbaa", the # is optional
+    pub fn from_hex(hex: &str) -> Result<Color, String> {
+        Rgba8::from_hex(hex).map(Color::from)
+    }
+
+    // HSV and HSL work on the sRGB encoded values, the same as color pickers.
+    // @params: h is in degrees, s, v and l are in [0, 1]
+    pub fn from_hsv(h: f32, s: f32, v: f32, a: f32) -> Color {
+        let chroma = v * s;
+        let [r, g, b] = hue_to_rgb(h, chroma, v - chroma);
+        Color::from_srgb(r, g, b, a)
+    }
+
+    pub fn from_hsl(h: f32, s: f32, l: f32, a: f32) -> Color {
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let [r, g, b] = hue_to_rgb(h, chroma, l - chroma / 2.0);
+        Color::from_srgb(r, g, b, a)
+    }
+
+    // (h in degrees [0, 360), s, v)
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let (h, max, _, chroma) = self.hue_max_min_chroma();
+        let s = if max == 0.0 { 0.0 } else { chroma / max };
+        (h, s, max)
+    }
+
+    // (h in degrees [0, 360), s, l)
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let (h, max, min, chroma) = self.hue_max_min_chroma();
+        let l = (max + min) / 2.0;
+        let s = if l == 0.0 || l == 1.0 {
+            0.0
+        } else {
+            chroma / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        (h, s, l)
+    }
+
+    fn hue_max_min_chroma(self) -> (f32, f32, f32, f32) {
+        let [r, g, b, _] = self.to_srgb();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+
+        let h = if chroma == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / chroma + 2.0)
+        } else {
+            60.0 * ((r - g) / chroma + 4.0)
+        };
+
+        (h, max, min, chroma)
+    }
+
+    // Color for blending with glBlendFunc(GL_ONE, GL_ONE_MINUS_SRC_ALPHA)
+    pub fn premultiplied(self) -> Color {
+        Color::new(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+    }
+
+    // Undoes premultiplied, fully transparent colors become TRANSPARENT
+    pub fn unpremultiplied(self) -> Color {
+        if self.a == 0.0 {
+            return Color::TRANSPARENT;
+        }
+
+        Color::new(self.r / self.a, self.g / self.a, self.b / self.a, self.a)
+    }
+
+    // In linear space, so halfway between red and green isn't too dark.
+    // t = 0 gives self, t = 1 gives other.
+    pub fn lerp(self, other: Color, t: f32) -> Color {
+        let (a, b) = (Vector4::from(self), Vector4::from(other));
+        Color::from(a + (b - a) * t)
+    }
+
+    pub fn with_alpha(self, a: f32) -> Color {
+        Color { a, ..self }
+    }
+
+    pub fn as_array(&self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    pub fn as_ptr(&self) -> *const f32 {
+        self as *const Color as *const f32
+    }
+}
+
+// Hue sector to rgb with the given chroma, m is added to every channel
+fn hue_to_rgb(h: f32, chroma: f32, m: f32) -> [f32; 3] {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    [r + m, g + m, b + m]
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Color::BLACK
+    }
+}
+
+impl Rgba8 {
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    // "#rgb", "#rgba", "#rrggbb" or "#rrggbbaa", the # is optional
+    pub fn from_hex(hex: &str) -> Result<Rgba8, String> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+
+        // from_str_radix alone would also accept a leading +
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid hex color {:?}", hex));
+        }
+
+        // Hex digits per channel, #f80 is short for #ff8800
+        let len = match digits.len() {
+            3 | 4 => 1,
+            6 | 8 => 2,
+            _ => {
+                return Err(format!(
+                    "Invalid hex color {:?}, expected 3, 4, 6 or 8 digits",
+                    hex
+                ))
+            }
+        };
+
+        let mut channels = [255u8; 4];
+        for (i, chunk) in digits.as_bytes().chunks(len).enumerate() {
+            // Safe to unwrap, the digits were checked above
+            let chunk = std::str::from_utf8(chunk).unwrap();
+            let value = u8::from_str_radix(chunk, 16).unwrap();
+
+            channels[i] = if len == 1 { value * 17 } else { value };
+        }
+
+        let [r, g, b, a] = channels;
+        Ok(Rgba8::new(r, g, b, a))
+    }
+
+    // Always "#rrggbbaa"
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+    }
+
+    pub fn as_array(&self) -> [u8; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+}
+
+// sRGB decode, alpha is linear in both
+impl From<Rgba8> for Color {
+    fn from(c: Rgba8) -> Color {
+        Color::from_srgb(
+            c.r as f32 / 255.0,
+            c.g as f32 / 255.0,
+            c.b as f32 / 255.0,
+            c.a as f32 / 255.0,
+        )
+    }
+}
+
+// sRGB encode, out of range values are clamped
+impl From<Color> for Rgba8 {
+    fn from(c: Color) -> Rgba8 {
+        let [r, g, b, a] = c
+            .to_srgb()
+            .map(|n| (n.clamp(0.0, 1.0) * 255.0).round() as u8);
+        Rgba8::new(r, g, b, a)
+    }
+}
+
+impl From<Color> for Vector4 {
+    fn from(c: Color) -> Vector4 {
+        Vector4::new(c.r, c.g, c.b, c.a)
+    }
+}
+
+impl From<Vector4> for Color {
+    fn from(v: Vector4) -> Color {
+        Color::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+// Opaque
+impl From<Vector3> for Color {
+    fn from(v: Vector3) -> Color {
+        Color::rgb(v.x, v.y, v.z)
+    }
+}
+
+impl From<Color> for Vector3 {
+    fn from(c: Color) -> Vector3 {
+        Vector3::new(c.r, c.g, c.b)
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod color_tests {
+    use crate::assert_abs_diff_eq;
+    use crate::color::*;
+
+    #[test]
+    fn test_transfer_functions() {
+        assert_eq!(srgb_to_linear(0.0), 0.0);
+        assert_eq!(srgb_to_linear(1.0), 1.0);
+        assert_abs_diff_eq!(srgb_to_linear(0.5), 0.214_041, 1e-4);
+
+        // Both sides of the linear segment
+        assert_abs_diff_eq!(srgb_to_linear(0.04), 0.04 / 12.92, 1e-4);
+        assert_abs_diff_eq!(linear_to_srgb(0.002), 0.002 * 12.92, 1e-4);
+
+        for i in 0..=100 {
+            let c = i as f32 / 100.0;
+            assert_abs_diff_eq!(linear_to_srgb(srgb_to_linear(c)), c, 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_rgba8_round_trip() {
+        for i in 0..=255u8 {
+            let c = Rgba8::new(i, 255 - i, i / 2, i);
+            assert_eq!(Rgba8::from(Color::from(c)), c);
+        }
+
+        // Mid gray in sRGB is much darker in linear
+        let gray = Color::from(Rgba8::new(128, 128, 128, 255));
+        assert!(gray.r < 0.25);
+    }
+
+    #[test]
+    fn test_hex_parsing() {
+        assert_eq!(
+            Rgba8::from_hex("#ff8800cc"),
+            Ok(Rgba8::new(255, 136, 0, 204))
+        );
+        assert_eq!(Rgba8::from_hex("ff8800"), Ok(Rgba8::new(255, 136, 0, 255)));
+        assert_eq!(Rgba8::from_hex("#F80"), Ok(Rgba8::new(255, 136, 0, 255)));
+        assert_eq!(Rgba8::from_hex("#f80c"), Ok(Rgba8::new(255, 136, 0, 204)));
+
+        assert!(Rgba8::from_hex("#ff880").is_err());
+        assert!(Rgba8::from_hex("#gg8800").is_err());
+        assert!(Rgba8::from_hex("").is_err());
+        assert!(Rgba8::from_hex("#+f+f+f").is_err());
+        assert!(Rgba8::from_hex("#ffé00").is_err());
+
+        assert_eq!(Rgba8::new(255, 136, 0, 204).to_hex(), "#ff8800cc");
+        assert_eq!(Color::from_hex("#ffffffff"), Ok(Color::WHITE));
+    }
+
+    #[test]
+    fn test_hsv() {
+        let orange = Color::from_hsv(30.0, 1.0, 1.0, 1.0);
+        assert_eq!(Rgba8::from(orange), Rgba8::new(255, 128, 0, 255));
+
+        let (h, s, v) = orange.to_hsv();
+        assert_abs_diff_eq!(h, 30.0, 1e-4);
+        assert_abs_diff_eq!(s, 1.0, 1e-4);
+        assert_abs_diff_eq!(v, 1.0, 1e-4);
+
+        // Hue wraps around
+        assert_eq!(
+            Rgba8::from(Color::from_hsv(-120.0, 1.0, 1.0, 1.0)),
+            Rgba8::new(0, 0, 255, 255)
+        );
+
+        // Gray has no hue or saturation
+        assert_eq!(Color::from_srgb(0.5, 0.5, 0.5, 1.0).to_hsv().1, 0.0);
+        assert_eq!(Color::BLACK.to_hsv(), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_hsl() {
+        for (h, s, l) in [(0.0, 1.0, 0.5), (200.0, 0.4, 0.3), (330.0, 0.8, 0.9)] {
+            let (h2, s2, l2) = Color::from_hsl(h, s, l, 1.0).to_hsl();
+            assert_abs_diff_eq!(h2, h, 1e-4);
+            assert_abs_diff_eq!(s2, s, 1e-4);
+            assert_abs_diff_eq!(l2, l, 1e-4);
+        }
+
+        let (h, s, l) = Color::WHITE.to_hsl();
+        assert_eq!((h, s), (0.0, 0.0));
+        assert_abs_diff_eq!(l, 1.0, 1e-4);
+        assert_eq!(
+            Rgba8::from(Color::from_hsl(120.0, 1.0, 0.25, 1.0)),
+            Rgba8::new(0, 128, 0, 255)
+        );
+    }
+
+    #[test]
+    fn test_premultiplied() {
+        let c = Color::new(0.8, 0.4, 0.2, 0.5);
+
+        assert_eq!(c.premultiplied(), Color::new(0.4, 0.2, 0.1, 0.5));
+        assert_eq!(c.premultiplied().unpremultiplied(), c);
+        assert_eq!(
+            Color::new(1.0, 1.0, 1.0, 0.0).unpremultiplied(),
+            Color::TRANSPARENT
+        );
+    }
+
+    #[test]
+    fn test_lerp_is_linear() {
+        let mid = Color::RED.lerp(Color::GREEN, 0.5);
+        assert_eq!(mid, Color::new(0.5, 0.5, 0.0, 1.0));
+
+        // Encoded for display that's brighter than a naive sRGB average of 128
+        let encoded = Rgba8::from(mid);
+        assert_eq!(encoded, Rgba8::new(188, 188, 0, 255));
+
+        assert_eq!(Color::RED.lerp(Color::GREEN, 0.0), Color::RED);
+    }
+}
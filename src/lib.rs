@@ -1,24 +1,47 @@
 //Wrapper for the gl wrapper (lol) for OpenGL
 //@TODO: Make a window class
+// Without the "gl" feature only the CPU side (math, color) is built, with no gl or glfw
+#[cfg(feature = "gl")]
 extern crate gl;
+#[cfg(feature = "gl")]
 extern crate glfw;
 
+#[cfg(feature = "gl")]
 use gl::types::*;
 
+#[cfg(feature = "gl")]
+use color::Color;
+
+#[cfg(feature = "gl")]
 pub mod buffer;
+pub mod color;
 pub mod error_handling;
 pub mod math;
+#[cfg(feature = "gl")]
 pub mod shader;
+#[cfg(feature = "gl")]
 pub mod texture;
+#[cfg(feature = "gl")]
 pub mod vertex_array;
+#[cfg(feature = "gl")]
 pub mod window;
 
-pub fn clear_color(r: f32, g: f32, b: f32, a: f32) {
+// Colors are linear, call enable_framebuffer_srgb so they get encoded on write
+#[cfg(feature = "gl")]
+pub fn clear_color(color: Color) {
     unsafe {
-        gl::ClearColor(r, g, b, a);
+        gl::ClearColor(color.r, color.g, color.b, color.a);
     }
 }
 
+//Linear values written to an sRGB framebuffer get encoded by the GPU
+#[cfg(feature = "gl")]
+pub fn enable_framebuffer_srgb() {
+    unsafe { gl::Enable(gl::FRAMEBUFFER_SRGB) }
+}
+
+#[cfg(feature = "gl")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PolygonMode {
     //Show only points
     Point = gl::POINT as isize,
@@ -30,6 +53,44 @@ pub enum PolygonMode {
     Fill = gl::FILL as isize,
 }
 
+#[cfg(feature = "gl")]
 pub fn polygon_mode(mode: PolygonMode) {
     unsafe { gl::PolygonMode(gl::FRONT_AND_BACK, mode as GLenum) }
 }
+
+// ###########################  TESTS  ####################################################################
+#[cfg(all(test, feature = "gl", feature = "serde"))]
+mod serde_tests {
+    use crate::buffer::BufferType;
+    use crate::texture::TextureOptionValue;
+    use crate::PolygonMode;
+    use gl::types::GLenum;
+
+    #[test]
+    fn test_gl_enums_are_strings() {
+        assert_eq!(
+            serde_json::to_string(&PolygonMode::Line).unwrap(),
+            "\"Line\""
+        );
+        assert_eq!(ron::to_string(&PolygonMode::Fill).unwrap(), "Fill");
+        let mode: PolygonMode = ron::from_str("Point").unwrap();
+        assert_eq!(mode as GLenum, gl::POINT);
+
+        let json = serde_json::to_string(&TextureOptionValue::ClampToEdge).unwrap();
+        assert_eq!(json, "\"ClampToEdge\"");
+        let value: TextureOptionValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(value as GLenum, gl::CLAMP_TO_EDGE);
+
+        assert_eq!(
+            serde_json::to_string(&BufferType::ElementArray).unwrap(),
+            "\"ElementArray\""
+        );
+        assert_eq!(
+            serde_json::from_str::<BufferType>("\"Array\"").unwrap(),
+            BufferType::Array
+        );
+
+        // Names, not the GL values
+        assert!(serde_json::from_str::<BufferType>(&gl::ARRAY_BUFFER.to_string()).is_err());
+    }
+}
@@ -1,49 +1,310 @@
+use std::ops::{
+    Add, AddAssign, Deref, DerefMut, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub,
+    SubAssign,
+};
+
+mod angle;
+mod approx;
+mod curve;
+pub mod easing;
+mod frustum;
+mod geometry;
+#[cfg(feature = "glam")]
+mod glam_interop;
+#[cfg(feature = "mint")]
+mod mint_interop;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_interop;
+pub mod noise;
+pub mod packing;
+pub mod polygon;
+mod quaternion;
+pub mod sampling;
+mod scalar;
+mod screen;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod simd;
+mod transform;
+
+pub use angle::{Deg, Rad};
+pub use approx::ApproxEq;
+pub use curve::{
+    ArcLengthTable, BSpline, CatmullRom, CubicBezier, Curve, Hermite, QuadraticBezier,
+};
+pub use frustum::{Frustum, VisibilityMask};
+pub use geometry::{Aabb, Obb, Plane, PlaneSide, Ray, Sphere, Triangle};
+pub use quaternion::{EulerOrder, Quaternion};
+pub use scalar::{Float, Scalar};
+pub use screen::{project, ray_from_cursor, unproject, DepthRange, Viewport};
+pub use transform::{Transform, WorldTransform};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Axis {
     X,
     Y,
     Z,
 }
 
-pub struct Vector2 {
-    pub x: f32,
-    pub y: f32,
+// N components of type T, see the aliases below for the usual ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Vector<T, const N: usize>([T; N]);
+
+// R rows by C columns, stored as C column vectors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Matrix<T, const R: usize, const C: usize>([Vector<T, R>; C]);
+
+pub type Vector2 = Vector<f32, 2>;
+pub type Vector3 = Vector<f32, 3>;
+pub type Vector4 = Vector<f32, 4>;
+
+pub type Vector2d = Vector<f64, 2>;
+pub type Vector3d = Vector<f64, 3>;
+pub type Vector4d = Vector<f64, 4>;
+
+pub type Vector2i = Vector<i32, 2>;
+pub type Vector3i = Vector<i32, 3>;
+pub type Vector4i = Vector<i32, 4>;
+
+pub type Vector2u = Vector<u32, 2>;
+pub type Vector3u = Vector<u32, 3>;
+pub type Vector4u = Vector<u32, 4>;
+
+pub type Matrix2 = Matrix<f32, 2, 2>;
+pub type Matrix3 = Matrix<f32, 3, 3>;
+pub type Matrix4 = Matrix<f32, 4, 4>;
+
+pub type Matrix2d = Matrix<f64, 2, 2>;
+pub type Matrix3d = Matrix<f64, 3, 3>;
+pub type Matrix4d = Matrix<f64, 4, 4>;
+
+pub type Matrix2i = Matrix<i32, 2, 2>;
+pub type Matrix3i = Matrix<i32, 3, 3>;
+pub type Matrix4i = Matrix<i32, 4, 4>;
+
+pub type Matrix2u = Matrix<u32, 2, 2>;
+pub type Matrix3u = Matrix<u32, 3, 3>;
+pub type Matrix4u = Matrix<u32, 4, 4>;
+
+// Named fields for vectors with 2, 3 and 4 components (v.x, v.y, ...) through Deref.
+// Matrices deref the same way, m.x is the first column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct XY<T> {
+    pub x: T,
+    pub y: T,
 }
 
-#[derive(Debug)]
-pub struct Vector3 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct XYZ<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
 }
 
-#[derive(Debug)]
-pub struct Vector4 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
-    pub w: f32,
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct XYZW<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+macro_rules! impl_named_fields {
+    ($N:literal, $Fields:ident) => {
+        impl<T> Deref for Vector<T, $N> {
+            type Target = $Fields<T>;
+
+            fn deref(&self) -> &$Fields<T> {
+                // SAFETY: both are repr(C) and hold exactly $N Ts, so the layouts match
+                unsafe { &*(self as *const Vector<T, $N> as *const $Fields<T>) }
+            }
+        }
+
+        impl<T> DerefMut for Vector<T, $N> {
+            fn deref_mut(&mut self) -> &mut $Fields<T> {
+                // SAFETY: see deref
+                unsafe { &mut *(self as *mut Vector<T, $N> as *mut $Fields<T>) }
+            }
+        }
+
+        impl<T, const R: usize> Deref for Matrix<T, R, $N> {
+            type Target = $Fields<Vector<T, R>>;
+
+            fn deref(&self) -> &$Fields<Vector<T, R>> {
+                // SAFETY: both are repr(C) and hold exactly $N columns, so the layouts match
+                unsafe { &*(self as *const Matrix<T, R, $N> as *const $Fields<Vector<T, R>>) }
+            }
+        }
+
+        impl<T, const R: usize> DerefMut for Matrix<T, R, $N> {
+            fn deref_mut(&mut self) -> &mut $Fields<Vector<T, R>> {
+                // SAFETY: see deref
+                unsafe { &mut *(self as *mut Matrix<T, R, $N> as *mut $Fields<Vector<T, R>>) }
+            }
+        }
+    };
+}
+
+impl_named_fields! { 2, XY }
+impl_named_fields! { 3, XYZ }
+impl_named_fields! { 4, XYZW }
+
+impl<T, const N: usize> Vector<T, N> {
+    pub const fn from_array(arr: [T; N]) -> Self {
+        Self(arr)
+    }
+
+    pub fn as_ptr(&self) -> *const T {
+        self.0.as_ptr()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Vector<U, N> {
+        Vector(self.0.map(f))
+    }
+}
+
+impl<T: Scalar, const N: usize> Vector<T, N> {
+    pub fn splat(n: T) -> Self {
+        Self([n; N])
+    }
+
+    pub fn zero() -> Self {
+        Self::splat(T::ZERO)
+    }
+
+    // Make this consuming or no? probably no
+    pub fn as_array(&self) -> [T; N] {
+        self.0
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: Scalar types are plain numbers, so there is no padding to read
+        unsafe {
+            std::slice::from_raw_parts(self.as_ptr() as *const u8, std::mem::size_of::<Self>())
+        }
+    }
+
+    fn zip_map(self, other: Self, mut f: impl FnMut(T, T) -> T) -> Self {
+        Self(std::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+
+    pub fn dot(self, other: Self) -> T {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .fold(T::ZERO, |sum, (a, b)| sum + *a * *b)
+    }
+
+    pub fn length_squared(self) -> T {
+        self.dot(self)
+    }
+
+    pub fn min(self, other: Self) -> Self {
+        self.zip_map(other, |a, b| if b < a { b } else { a })
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        self.zip_map(other, |a, b| if b > a { b } else { a })
+    }
+
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        self.max(min).min(max)
+    }
+
+    // Lossless conversion to a wider element type, e.g. Vector3i to Vector3d
+    pub fn convert<U: From<T>>(self) -> Vector<U, N> {
+        self.map(U::from)
+    }
+
+    // Lossy conversion that behaves like `as`, e.g. Vector3 to Vector3i truncates
+    pub fn cast<U: Scalar>(self) -> Vector<U, N> {
+        self.map(|n| U::from_f64(n.to_f64()))
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> {
+    fn from(arr: [T; N]) -> Self {
+        Self(arr)
+    }
+}
+
+impl<T, const N: usize> From<Vector<T, N>> for [T; N] {
+    fn from(vec: Vector<T, N>) -> Self {
+        vec.0
+    }
+}
+
+impl<T> Vector<T, 2> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self([x, y])
+    }
 }
 
 #[allow(dead_code)]
-impl Vector2 {
-    pub fn new(x: f32, y: f32) -> Self {
-        Self { x, y }
+impl<T: Scalar> Vector<T, 2> {
+    // 2D cross product, the z component of the 3D cross of (x, y, 0) vectors
+    pub fn cross(self, other: Vector<T, 2>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn extend(self, z: T) -> Vector<T, 3> {
+        Vector::<T, 3>::new(self.x, self.y, z)
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Vector<T, 2> {
+    // Rotated 90 degrees counter clockwise
+    pub fn perp(self) -> Vector<T, 2> {
+        Vector::<T, 2>::new(-self.y, self.x)
+    }
+}
+
+impl<T> Vector<T, 3> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self([x, y, z])
     }
 }
 
 #[allow(dead_code)]
-impl Vector3 {
-    pub fn new(x: f32, y: f32, z: f32) -> Self {
-        Self { x, y, z }
+impl<T: Scalar> Vector<T, 3> {
+    pub fn cross(self, other: Vector<T, 3>) -> Vector<T, 3> {
+        Vector::<T, 3>::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn xy(self) -> Vector<T, 2> {
+        Vector::<T, 2>::new(self.x, self.y)
+    }
+
+    pub fn extend(self, w: T) -> Vector<T, 4> {
+        Vector::<T, 4>::new(self.x, self.y, self.z, w)
+    }
+
+    pub fn truncate(self) -> Vector<T, 2> {
+        self.xy()
     }
 }
 
-impl Vector4 {
-    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
-        Self { x, y, z, w }
+impl<T> Vector<T, 4> {
+    pub const fn new(x: T, y: T, z: T, w: T) -> Self {
+        Self([x, y, z, w])
     }
+}
 
-    fn get_index(&self, i: usize) -> f32 {
+impl<T: Scalar> Vector<T, 4> {
+    fn get_index(&self, i: usize) -> T {
         match i {
             0 => self.x,
             1 => self.y,
@@ -53,31 +314,26 @@ impl Vector4 {
         }
     }
 
-    // Make this consuming or no? probably no
-    pub fn as_array(&self) -> [f32; 4] {
-        [self.x, self.y, self.z, self.w]
-    }
-
-    pub fn add_scalar(&mut self, n: f32) {
+    pub fn add_scalar(&mut self, n: T) {
         //self.iter();
         self.x += n;
         self.y += n;
         self.z += n;
         self.w += n;
     }
-    pub fn subtract_scalar(&mut self, n: f32) {
+    pub fn subtract_scalar(&mut self, n: T) {
         self.x -= n;
         self.y -= n;
         self.z -= n;
         self.w -= n;
     }
-    pub fn multiply_scalar(&mut self, n: f32) {
+    pub fn multiply_scalar(&mut self, n: T) {
         self.x *= n;
         self.y *= n;
         self.z *= n;
         self.w *= n;
     }
-    pub fn divide_scalar(&mut self, n: f32) {
+    pub fn divide_scalar(&mut self, n: T) {
         self.x /= n;
         self.y /= n;
         self.z /= n;
@@ -85,59 +341,68 @@ impl Vector4 {
     }
 
     // @params: trans must be a translation matrix created by that function in Matrix4
-    pub fn translate(&mut self, trans: Matrix4) {
+    pub fn translate(&mut self, trans: Matrix<T, 4, 4>) {
         self.x = multiply_col_with_row(self.as_array(), trans.get_row(0));
         self.y = multiply_col_with_row(self.as_array(), trans.get_row(1));
         self.z = multiply_col_with_row(self.as_array(), trans.get_row(2));
         self.w = multiply_col_with_row(self.as_array(), trans.get_row(3));
     }
 
-    #[allow(non_snake_case)]
-    pub fn rotate(&mut self, axis: Axis, angle: f32) {
-        let mat: Matrix4;
-        match axis {
-            Axis::X => {
-                mat = Matrix4::from_columns(
-                    Vector4::new(1.0, 0.0, 0.0, 0.0),
-                    Vector4::new(0.0, angle.cos(), angle.sin(), 0.0),
-                    Vector4::new(0.0, -1.0 * angle.sin(), angle.cos(), 0.0),
-                    Vector4::new(0.0, 0.0, 0.0, 1.0),
-                );
-            }
+    pub fn xy(self) -> Vector<T, 2> {
+        Vector::<T, 2>::new(self.x, self.y)
+    }
 
-            Axis::Y => {
-                mat = Matrix4::from_columns(
-                    Vector4::new(angle.cos(), 0.0, -1.0 * angle.sin(), 0.0),
-                    Vector4::new(0.0, 1.0, 0.0, 0.0),
-                    Vector4::new(angle.sin(), 0.0, angle.cos(), 0.0),
-                    Vector4::new(0.0, 0.0, 0.0, 1.0),
-                );
-            }
-            Axis::Z => {
-                mat = Matrix4::from_columns(
-                    Vector4::new(angle.cos(), angle.sin(), 0.0, 0.0),
-                    Vector4::new(-1.0 * angle.sin(), angle.cos(), 0.0, 0.0),
-                    Vector4::new(0.0, 0.0, 1.0, 0.0),
-                    Vector4::new(0.0, 0.0, 0.0, 1.0),
-                );
-            }
-        }
+    pub fn xyz(self) -> Vector<T, 3> {
+        Vector::<T, 3>::new(self.x, self.y, self.z)
+    }
+
+    // Drops w, does NOT divide by it
+    pub fn truncate(self) -> Vector<T, 3> {
+        self.xyz()
+    }
+}
 
-        self.x = multiply_col_with_row(self.as_array(), mat.get_row(0));
-        self.y = multiply_col_with_row(self.as_array(), mat.get_row(1));
-        self.z = multiply_col_with_row(self.as_array(), mat.get_row(2));
-        self.w = multiply_col_with_row(self.as_array(), mat.get_row(3));
+impl<T: Float> Vector<T, 4> {
+    #[allow(non_snake_case)]
+    pub fn rotate(&mut self, axis: Axis, angle: impl Into<Rad<T>>) {
+        let (zero, one) = (T::ZERO, T::ONE);
+        let (sin, cos) = angle.into().sin_cos();
+
+        let mat: Matrix<T, 4, 4> = match axis {
+            Axis::X => Matrix::<T, 4, 4>::from_columns(
+                Vector::<T, 4>::new(one, zero, zero, zero),
+                Vector::<T, 4>::new(zero, cos, sin, zero),
+                Vector::<T, 4>::new(zero, -sin, cos, zero),
+                Vector::<T, 4>::new(zero, zero, zero, one),
+            ),
+
+            Axis::Y => Matrix::<T, 4, 4>::from_columns(
+                Vector::<T, 4>::new(cos, zero, -sin, zero),
+                Vector::<T, 4>::new(zero, one, zero, zero),
+                Vector::<T, 4>::new(sin, zero, cos, zero),
+                Vector::<T, 4>::new(zero, zero, zero, one),
+            ),
+            Axis::Z => Matrix::<T, 4, 4>::from_columns(
+                Vector::<T, 4>::new(cos, sin, zero, zero),
+                Vector::<T, 4>::new(-sin, cos, zero, zero),
+                Vector::<T, 4>::new(zero, zero, one, zero),
+                Vector::<T, 4>::new(zero, zero, zero, one),
+            ),
+        };
+
+        // Every component has to be computed from the unrotated vector
+        *self = mat * *self;
     }
 
     pub fn normalize(&mut self) {
-        let length: f32 = self.get_length();
+        let length = self.get_length();
 
-        self.x = self.x / length;
-        self.y = self.y / length;
-        self.z = self.z / length;
+        self.x /= length;
+        self.y /= length;
+        self.z /= length;
     }
 
-    pub fn get_length(&self) -> f32 {
+    pub fn get_length(&self) -> T {
         ((self.x * self.x) + (self.y * self.y) + (self.z * self.z)).sqrt()
     }
 }
@@ -160,223 +425,259 @@ impl Vector4 {
 //     }
 // }
 
-#[allow(dead_code)]
-//Each vec here is a COLUMN
-pub struct Matrix2 {
-    pub x: Vector2,
-    pub y: Vector2,
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn as_ptr(&self) -> *const T {
+        self.0.as_ptr() as *const T
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the columns are repr(C) arrays back to back, so this is R * C Ts
+        // in column-major order, like OpenGL expects
+        unsafe { std::slice::from_raw_parts(self.as_ptr(), R * C) }
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Matrix<U, R, C> {
+        Matrix(self.0.map(|column| column.map(&mut f)))
+    }
 }
 
-#[allow(dead_code)]
-pub struct Matrix3 {
-    pub x: Vector3,
-    pub y: Vector3,
-    pub z: Vector3,
+impl<T: Scalar, const R: usize, const C: usize> Matrix<T, R, C> {
+    // Each inner array is a COLUMN
+    pub fn from_array(arr: [[T; R]; C]) -> Self {
+        Self(arr.map(Vector))
+    }
+
+    pub fn as_array(&self) -> [[T; R]; C] {
+        self.0.map(|column| column.0)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: Scalar types are plain numbers, so there is no padding to read
+        unsafe {
+            std::slice::from_raw_parts(self.as_ptr() as *const u8, std::mem::size_of::<Self>())
+        }
+    }
+
+    pub fn get_row(&self, i: usize) -> [T; C] {
+        if i >= R {
+            panic!("Index provided to get_row is out of bounds!");
+        }
+
+        self.0.map(|column| column.0[i])
+    }
+
+    pub fn get_column(&self, i: usize) -> [T; R] {
+        match self.0.get(i) {
+            Some(column) => column.0,
+            None => panic!("Index provided to get_column is out of bounds!"),
+        }
+    }
+
+    pub fn transpose(self) -> Matrix<T, C, R> {
+        Matrix(std::array::from_fn(|i| Vector(self.get_row(i))))
+    }
+
+    // Lossless conversion to a wider element type, e.g. Matrix4 to Matrix4d
+    pub fn convert<U: From<T>>(self) -> Matrix<U, R, C> {
+        self.map(U::from)
+    }
+
+    // Lossy conversion that behaves like `as`, e.g. Matrix4d to Matrix4 rounds
+    pub fn cast<U: Scalar>(self) -> Matrix<U, R, C> {
+        self.map(|n| U::from_f64(n.to_f64()))
+    }
 }
 
-pub struct Matrix4 {
-    pub x: Vector4,
-    pub y: Vector4,
-    pub z: Vector4,
-    pub w: Vector4,
+impl<T: Scalar, const N: usize> Matrix<T, N, N> {
+    pub fn identity() -> Self {
+        Self(std::array::from_fn(|column| {
+            Vector(std::array::from_fn(|row| {
+                if row == column {
+                    T::ONE
+                } else {
+                    T::ZERO
+                }
+            }))
+        }))
+    }
 }
 
 #[allow(dead_code)]
-impl Matrix2 {
-    pub fn new(c0r0: f32, c0r1: f32, c1r0: f32, c1r1: f32) -> Self {
-        Matrix2::from_columns(Vector2::new(c0r0, c0r1), Vector2::new(c1r0, c1r1))
+impl<T: Scalar> Matrix<T, 2, 2> {
+    pub fn new(c0r0: T, c0r1: T, c1r0: T, c1r1: T) -> Self {
+        Self::from_columns(
+            Vector::<T, 2>::new(c0r0, c0r1),
+            Vector::<T, 2>::new(c1r0, c1r1),
+        )
     }
 
-    pub fn from_columns(c0: Vector2, c1: Vector2) -> Matrix2 {
-        Matrix2 { x: c0, y: c1 }
+    pub fn from_columns(c0: Vector<T, 2>, c1: Vector<T, 2>) -> Self {
+        Matrix([c0, c1])
     }
+}
+
+impl<T: Float> Matrix<T, 2, 2> {
+    pub fn determinant(self) -> T {
+        self.x.x * self.y.y - self.y.x * self.x.y
+    }
+
+    // Returns None if the matrix is singular
+    pub fn inverse(self) -> Option<Self> {
+        let det = self.determinant();
 
-    pub fn identity() -> Matrix2 {
-        Matrix2::new(1.0, 0.0, 0.0, 1.0)
+        if det == T::ZERO {
+            return None;
+        }
+
+        Some(Self::new(self.y.y, -self.x.y, -self.y.x, self.x.x) / det)
     }
 }
 
 #[allow(dead_code)]
-impl Matrix3 {
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+impl<T: Scalar> Matrix<T, 3, 3> {
+    #[rustfmt::skip]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        c0r0: f32, c0r1: f32, c0r2: f32,
-        c1r0: f32, c1r1: f32, c1r2: f32,
-        c2r0: f32, c2r1: f32, c2r2: f32,
+        c0r0: T, c0r1: T, c0r2: T,
+        c1r0: T, c1r1: T, c1r2: T,
+        c2r0: T, c2r1: T, c2r2: T,
     ) -> Self {
-        Matrix3::from_columns(
-            Vector3::new(c0r0, c0r1, c0r2),
-            Vector3::new(c1r0, c1r1, c1r2),
-            Vector3::new(c2r0, c2r1, c2r2),
+        Self::from_columns(
+            Vector::<T, 3>::new(c0r0, c0r1, c0r2),
+            Vector::<T, 3>::new(c1r0, c1r1, c1r2),
+            Vector::<T, 3>::new(c2r0, c2r1, c2r2),
         )
     }
 
-    pub fn from_columns(c0: Vector3, c1: Vector3, c2: Vector3) -> Matrix3 {
-        Matrix3 {
-            x: c0,
-            y: c1,
-            z: c2,
-        }
+    pub fn from_columns(c0: Vector<T, 3>, c1: Vector<T, 3>, c2: Vector<T, 3>) -> Self {
+        Matrix([c0, c1, c2])
     }
 
-    pub fn identity() -> Matrix3 {
-        Matrix3::new(1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0)
+    // Upper left 3x3 of m, drops the translation
+    pub fn from_matrix4(m: Matrix<T, 4, 4>) -> Self {
+        Self::from_columns(m.x.xyz(), m.y.xyz(), m.z.xyz())
     }
 }
 
-impl Matrix4 {
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+impl<T: Float> Matrix<T, 3, 3> {
+    // Inverse-transpose of the upper 3x3 of the model matrix, used to transform normals.
+    // Returns None if the model matrix has a zero scale.
+    pub fn normal_matrix(model: Matrix<T, 4, 4>) -> Option<Self> {
+        Matrix::<T, 3, 3>::from_matrix4(model)
+            .inverse()
+            .map(|inverse| inverse.transpose())
+    }
+
+    pub fn determinant(self) -> T {
+        self.x.dot(self.y.cross(self.z))
+    }
+
+    // Returns None if the matrix is singular
+    pub fn inverse(self) -> Option<Self> {
+        let det = self.determinant();
+
+        if det == T::ZERO {
+            return None;
+        }
+
+        // The cross products are the rows of the adjugate
+        let adjugate = Matrix::<T, 3, 3>::from_columns(
+            self.y.cross(self.z),
+            self.z.cross(self.x),
+            self.x.cross(self.y),
+        )
+        .transpose();
+
+        Some(adjugate / det)
+    }
+}
+
+impl<T: Scalar> Matrix<T, 4, 4> {
+    #[rustfmt::skip]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        c0r0: f32, c0r1: f32, c0r2: f32, c0r3: f32,
-        c1r0: f32, c1r1: f32, c1r2: f32, c1r3: f32,
-        c2r0: f32, c2r1: f32, c2r2: f32, c2r3: f32,
-        c3r0: f32, c3r1: f32, c3r2: f32, c3r3: f32,
+        c0r0: T, c0r1: T, c0r2: T, c0r3: T,
+        c1r0: T, c1r1: T, c1r2: T, c1r3: T,
+        c2r0: T, c2r1: T, c2r2: T, c2r3: T,
+        c3r0: T, c3r1: T, c3r2: T, c3r3: T,
     ) -> Self {
-        Matrix4::from_columns(
-            Vector4::new(c0r0, c0r1, c0r2, c0r3),
-            Vector4::new(c1r0, c1r1, c1r2, c1r3),
-            Vector4::new(c2r0, c2r1, c2r2, c2r3),
-            Vector4::new(c3r0, c3r1, c3r2, c3r3),
+        Self::from_columns(
+            Vector::<T, 4>::new(c0r0, c0r1, c0r2, c0r3),
+            Vector::<T, 4>::new(c1r0, c1r1, c1r2, c1r3),
+            Vector::<T, 4>::new(c2r0, c2r1, c2r2, c2r3),
+            Vector::<T, 4>::new(c3r0, c3r1, c3r2, c3r3),
         )
     }
 
-    pub fn from_columns(c0: Vector4, c1: Vector4, c2: Vector4, c3: Vector4) -> Matrix4 {
-        Matrix4 {
-            x: c0,
-            y: c1,
-            z: c2,
-            w: c3,
-        }
+    pub fn from_columns(
+        c0: Vector<T, 4>,
+        c1: Vector<T, 4>,
+        c2: Vector<T, 4>,
+        c3: Vector<T, 4>,
+    ) -> Self {
+        Matrix([c0, c1, c2, c3])
     }
 
-    #[cfg_attr(rustfmt, rustfmt_skip)]
-    pub fn identity() -> Matrix4 {
-        Matrix4::new(
-            1.0, 0.0, 0.0, 0.0,
-            0.0, 1.0, 0.0, 0.0,
-            0.0, 0.0, 1.0, 0.0,
-            0.0, 0.0, 0.0, 1.0,
+    // m in the upper left 3x3, no translation
+    pub fn from_matrix3(m: Matrix<T, 3, 3>) -> Self {
+        Self::from_columns(
+            m.x.extend(T::ZERO),
+            m.y.extend(T::ZERO),
+            m.z.extend(T::ZERO),
+            Vector::<T, 4>::new(T::ZERO, T::ZERO, T::ZERO, T::ONE),
         )
     }
 
-    pub fn create_translation(identity: Matrix4, vec: Vector3) -> Matrix4 {
-        Matrix4::from_columns(
+    pub fn create_translation(identity: Self, vec: Vector<T, 3>) -> Self {
+        Self::from_columns(
             identity.x,
             identity.y,
             identity.z,
-            Vector4::new(vec.x, vec.y, vec.z, identity.w.w),
+            Vector::<T, 4>::new(vec.x, vec.y, vec.z, identity.w.w),
         )
     }
 
-    pub fn get_row(&self, i: usize) -> [f32; 4] {
-        if i > 3 {
-            panic!("Index provided to get_row is out of bounds!");
-        } else {
-            let row: [f32; 4] = [
-                self.x.get_index(i),
-                self.y.get_index(i),
-                self.z.get_index(i),
-                self.w.get_index(i),
-            ];
-
-            row
-        }
-
-        // // non-consuming
-        // pub fn to_array(self) -> [[f32; 4]; 4] {
-        //     let swag =
-        // }
-    }
-
-    pub fn get_column(&self, i: usize) -> [f32; 4] {
-        match i {
-            0 => self.x.as_array(),
-            1 => self.y.as_array(),
-            2 => self.z.as_array(),
-            3 => self.w.as_array(),
-            _ => panic!("Index provided to get_column is out of bounds!"),
-        }
+    pub fn from_translation(translation: Vector<T, 3>) -> Self {
+        Self::create_translation(Self::identity(), translation)
     }
 
-    pub fn add_scalar(&mut self, n: f32) {
+    pub fn add_scalar(&mut self, n: T) {
         self.x.add_scalar(n);
         self.y.add_scalar(n);
         self.z.add_scalar(n);
         self.w.add_scalar(n);
     }
 
-    pub fn subtract_scalar(&mut self, n: f32) {
+    pub fn subtract_scalar(&mut self, n: T) {
         self.x.subtract_scalar(n);
         self.y.subtract_scalar(n);
         self.z.subtract_scalar(n);
         self.w.subtract_scalar(n);
     }
 
-    pub fn multiply_scalar(&mut self, n: f32) {
+    pub fn multiply_scalar(&mut self, n: T) {
         self.x.multiply_scalar(n);
         self.y.multiply_scalar(n);
         self.z.multiply_scalar(n);
         self.w.multiply_scalar(n);
     }
 
-    pub fn divide_scalar(&mut self, n: f32) {
+    pub fn divide_scalar(&mut self, n: T) {
         self.x.divide_scalar(n);
         self.y.divide_scalar(n);
         self.z.divide_scalar(n);
         self.w.divide_scalar(n);
     }
 
-    // #[cfg_attr(rustfmt, rustfmt_skip)]
-    // remember that each Vector is a COLUMN
-    // traverse left to right of LEFT matrix , top to bottom of RIGHT matrix
-    // Consuming bc we dont want the old matrix
+    // self = self * m
     // @TODO: look into deleting this
-    pub fn multiply_matrix(&mut self, m: Matrix4) {
-        let col0 = Vector4::new(
-            multiply_col_with_row(m.get_column(0), self.get_row(0)),
-            multiply_col_with_row(m.get_column(0), self.get_row(1)),
-            multiply_col_with_row(m.get_column(0), self.get_row(2)),
-            multiply_col_with_row(m.get_column(0), self.get_row(3)),
-        );
-
-        let col1 = Vector4::new(
-            multiply_col_with_row(m.get_column(1), self.get_row(0)),
-            multiply_col_with_row(m.get_column(1), self.get_row(1)),
-            multiply_col_with_row(m.get_column(1), self.get_row(2)),
-            multiply_col_with_row(m.get_column(1), self.get_row(3)),
-        );
-
-        let col2 = Vector4::new(
-            multiply_col_with_row(m.get_column(2), self.get_row(0)),
-            multiply_col_with_row(m.get_column(2), self.get_row(1)),
-            multiply_col_with_row(m.get_column(2), self.get_row(2)),
-            multiply_col_with_row(m.get_column(2), self.get_row(3)),
-        );
-
-        let col3 = Vector4::new(
-            multiply_col_with_row(m.get_column(3), self.get_row(0)),
-            multiply_col_with_row(m.get_column(3), self.get_row(1)),
-            multiply_col_with_row(m.get_column(3), self.get_row(2)),
-            multiply_col_with_row(m.get_column(3), self.get_row(3)),
-        );
-
-        self.x = col0;
-        self.y = col1;
-        self.z = col2;
-        self.w = col3;
-    }
-
-    pub fn as_array(&self) -> [[f32; 4]; 4] {
-        [
-            self.x.as_array(),
-            self.y.as_array(),
-            self.z.as_array(),
-            self.w.as_array(),
-        ]
+    pub fn multiply_matrix(&mut self, m: Self) {
+        *self *= m;
     }
 
-    pub fn to_ptr(&self) -> *const f32 {
-        &self.as_array()[0][0]
+    #[deprecated(note = "use as_ptr")]
+    pub fn to_ptr(&self) -> *const T {
+        self.as_ptr()
     }
 
     pub fn print(&self) {
@@ -394,135 +695,837 @@ impl Matrix4 {
     }
 }
 
-fn multiply_col_with_row(col: [f32; 4], row: [f32; 4]) -> f32 {
-    let mut sum = 0.0;
-
-    for i in 0..col.len() {
-        sum += col[i] * row[i];
+impl<T: Float> Matrix<T, 4, 4> {
+    // Right handed view matrix, the camera looks down -z (OpenGL convention)
+    #[rustfmt::skip]
+    pub fn look_at_rh(eye: Vector<T, 3>, target: Vector<T, 3>, up: Vector<T, 3>) -> Self {
+        let forward = (target - eye).normalized();
+        let right = forward.cross(up).normalized();
+        let up = right.cross(forward);
+        let (zero, one) = (T::ZERO, T::ONE);
+
+        Self::new(
+            right.x, up.x, -forward.x, zero,
+            right.y, up.y, -forward.y, zero,
+            right.z, up.z, -forward.z, zero,
+            -right.dot(eye), -up.dot(eye), forward.dot(eye), one,
+        )
     }
 
-    sum
-}
-
-pub fn convert_to_radians(angle: f32) -> f32 {
-    angle * (std::f32::consts::PI / 180.0)
-}
-
-pub fn convert_to_degrees(radians: f32) -> f32 {
-    radians * (180.0 / std::f32::consts::PI)
-}
-
-// ###########################  TESTS  ####################################################################
-#[cfg(test)]
-mod vector_tests {
-    use crate::math::Axis;
-    use crate::math::Vector3;
-    use crate::math::Vector4;
-
-    use super::Matrix4;
-    #[test]
-    fn test_get_index() {
-        let vec = Vector4::new(0.0, 1.0, 2.0, 3.0);
-
-        assert_eq!(0.0, vec.get_index(0));
-        assert_eq!(1.0, vec.get_index(1));
-        assert_eq!(2.0, vec.get_index(2));
-        assert_eq!(3.0, vec.get_index(3));
+    // Left handed view matrix, the camera looks down +z
+    #[rustfmt::skip]
+    pub fn look_at_lh(eye: Vector<T, 3>, target: Vector<T, 3>, up: Vector<T, 3>) -> Self {
+        let forward = (target - eye).normalized();
+        let right = up.cross(forward).normalized();
+        let up = forward.cross(right);
+        let (zero, one) = (T::ZERO, T::ONE);
+
+        Self::new(
+            right.x, up.x, forward.x, zero,
+            right.y, up.y, forward.y, zero,
+            right.z, up.z, forward.z, zero,
+            -right.dot(eye), -up.dot(eye), -forward.dot(eye), one,
+        )
     }
 
-    #[test]
-    fn test_as_array() {
-        let vec = Vector4::new(0.9, 0.9, 0.9, 0.9);
-        let vec_arr = vec.as_array();
+    // Camera (right, up, forward) in world space from a view matrix made by look_at_rh.
+    // The view matrix must not contain scale.
+    pub fn view_basis_rh(&self) -> (Vector<T, 3>, Vector<T, 3>, Vector<T, 3>) {
+        let (right, up, back) = self.view_rows();
+        (right, up, -back)
+    }
 
-        for i in vec_arr {
-            assert_eq!(i, 0.9);
-        }
+    // Camera (right, up, forward) in world space from a view matrix made by look_at_lh
+    pub fn view_basis_lh(&self) -> (Vector<T, 3>, Vector<T, 3>, Vector<T, 3>) {
+        self.view_rows()
     }
 
-    #[test]
-    fn test_add_scalar() {
-        let mut vec = Vector4::new(0.5, 0.5, 0.6, 0.2);
-        vec.add_scalar(2.0);
+    // The rows of the rotation part of a view matrix are the camera axes
+    fn view_rows(&self) -> (Vector<T, 3>, Vector<T, 3>, Vector<T, 3>) {
+        let transposed = Matrix::<T, 3, 3>::from_matrix4(*self).transpose();
+        (transposed.x, transposed.y, transposed.z)
+    }
 
-        let vec = vec.as_array();
+    pub fn determinant(self) -> T {
+        let m = self.as_array();
+        let (s, c) = sub_determinants(&m);
 
-        assert_eq!(vec[0], 2.5);
-        assert_eq!(vec[1], 2.5);
-        assert_eq!(vec[2], 2.6);
-        assert_eq!(vec[3], 2.2);
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
     }
 
-    #[test]
-    fn test_subtract_scalar() {
-        let mut vec = Vector4::new(1.0, 1.0, 1.0, 1.0);
-        vec.subtract_scalar(2.0);
+    // Full cofactor inverse, returns None if the matrix is singular.
+    // Prefer inverse_affine for model and view matrices.
+    pub fn inverse(self) -> Option<Self> {
+        let m = self.as_array();
+        let (s, c) = sub_determinants(&m);
 
-        let vec = vec.as_array();
+        let det = s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0];
 
-        for i in vec {
-            assert_eq!(i, -1.0);
+        if det == T::ZERO {
+            return None;
         }
+
+        let inv_det = T::ONE / det;
+
+        let inverse = [
+            [
+                (m[1][1] * c[5] - m[1][2] * c[4] + m[1][3] * c[3]) * inv_det,
+                (-m[0][1] * c[5] + m[0][2] * c[4] - m[0][3] * c[3]) * inv_det,
+                (m[3][1] * s[5] - m[3][2] * s[4] + m[3][3] * s[3]) * inv_det,
+                (-m[2][1] * s[5] + m[2][2] * s[4] - m[2][3] * s[3]) * inv_det,
+            ],
+            [
+                (-m[1][0] * c[5] + m[1][2] * c[2] - m[1][3] * c[1]) * inv_det,
+                (m[0][0] * c[5] - m[0][2] * c[2] + m[0][3] * c[1]) * inv_det,
+                (-m[3][0] * s[5] + m[3][2] * s[2] - m[3][3] * s[1]) * inv_det,
+                (m[2][0] * s[5] - m[2][2] * s[2] + m[2][3] * s[1]) * inv_det,
+            ],
+            [
+                (m[1][0] * c[4] - m[1][1] * c[2] + m[1][3] * c[0]) * inv_det,
+                (-m[0][0] * c[4] + m[0][1] * c[2] - m[0][3] * c[0]) * inv_det,
+                (m[3][0] * s[4] - m[3][1] * s[2] + m[3][3] * s[0]) * inv_det,
+                (-m[2][0] * s[4] + m[2][1] * s[2] - m[2][3] * s[0]) * inv_det,
+            ],
+            [
+                (-m[1][0] * c[3] + m[1][1] * c[1] - m[1][2] * c[0]) * inv_det,
+                (m[0][0] * c[3] - m[0][1] * c[1] + m[0][2] * c[0]) * inv_det,
+                (-m[3][0] * s[3] + m[3][1] * s[1] - m[3][2] * s[0]) * inv_det,
+                (m[2][0] * s[3] - m[2][1] * s[1] + m[2][2] * s[0]) * inv_det,
+            ],
+        ];
+
+        Some(Matrix::from_array(inverse))
     }
 
-    #[test]
-    fn test_multiply_scalar() {
-        let mut vec = Vector4::new(2.0, 2.0, 2.0, 2.0);
-        vec.multiply_scalar(0.5);
+    // Fast inverse for matrices whose bottom row is 0 0 0 1 (rotation, scale, translation).
+    // Only the upper 3x3 gets a real inverse, returns None if that part is singular.
+    pub fn inverse_affine(self) -> Option<Self> {
+        let inverse = Matrix::<T, 3, 3>::from_matrix4(self).inverse()?;
+        let translation = -(inverse * self.w.xyz());
 
-        let vec = vec.as_array();
+        let mut result = Self::from_matrix3(inverse);
+        result.w = translation.extend(T::ONE);
 
-        for i in vec {
-            assert_eq!(i, 1.0);
-        }
+        Some(result)
     }
+}
 
-    #[test]
-    fn test_divide_scalar() {
-        todo!()
+impl Matrix4 {
+    // All projections below are right handed (camera looks down -z) and map into
+    // OpenGL clip space with NDC z in [-1, 1], unless the name says otherwise.
+    // @params: fov_y is the vertical field of view, takes Rad or Deg
+    #[rustfmt::skip]
+    pub fn perspective(fov_y: impl Into<Rad>, aspect: f32, near: f32, far: f32) -> Matrix4 {
+        let f = 1.0 / (fov_y.into() / 2.0).tan();
+
+        Matrix4::new(
+            f / aspect, 0.0, 0.0, 0.0,
+            0.0, f, 0.0, 0.0,
+            0.0, 0.0, (far + near) / (near - far), -1.0,
+            0.0, 0.0, (2.0 * far * near) / (near - far), 0.0,
+        )
     }
 
-    #[test]
-    fn test_normalize() {
-        let mut vec = Vector4::new(3.0, 5.0, 7.0, 1.0);
-        vec.normalize();
+    // perspective() with far taken to infinity
+    #[rustfmt::skip]
+    pub fn perspective_infinite(fov_y: impl Into<Rad>, aspect: f32, near: f32) -> Matrix4 {
+        let f = 1.0 / (fov_y.into() / 2.0).tan();
 
-        assert_eq!((vec.x * 1000000.0).round() / 1000000.0, 0.329293);
-        assert_eq!((vec.y * 1000000.0).round() / 1000000.0, 0.548821);
-        assert_eq!((vec.z * 100000.0).round() / 100000.0, 0.76835);
+        Matrix4::new(
+            f / aspect, 0.0, 0.0, 0.0,
+            0.0, f, 0.0, 0.0,
+            0.0, 0.0, -1.0, -1.0,
+            0.0, 0.0, -2.0 * near, 0.0,
+        )
     }
 
-    #[test]
-    fn test_get_length() {
-        let vec = Vector4::new(5.0, 7.0, 3.0, 1.0);
-        let length = vec.get_length();
+    // Reversed-Z with NDC z in [0, 1]: near maps to 1 and far maps to 0.
+    // Needs glClipControl(GL_LOWER_LEFT, GL_ZERO_TO_ONE) and glDepthFunc(GL_GREATER).
+    #[rustfmt::skip]
+    pub fn perspective_reversed_z(fov_y: impl Into<Rad>, aspect: f32, near: f32, far: f32) -> Matrix4 {
+        let f = 1.0 / (fov_y.into() / 2.0).tan();
 
-        assert_eq!((length * 100000.0).round() / 100000.0, 9.11043);
+        Matrix4::new(
+            f / aspect, 0.0, 0.0, 0.0,
+            0.0, f, 0.0, 0.0,
+            0.0, 0.0, near / (far - near), -1.0,
+            0.0, 0.0, (far * near) / (far - near), 0.0,
+        )
     }
 
-    #[test]
-    fn test_rotate() {
-        let mut vec = Vector4::new(5.0, 5.0, 9.0, 1.0);
+    // perspective_reversed_z() with far taken to infinity
+    #[rustfmt::skip]
+    pub fn perspective_infinite_reversed_z(fov_y: impl Into<Rad>, aspect: f32, near: f32) -> Matrix4 {
+        let f = 1.0 / (fov_y.into() / 2.0).tan();
 
-        vec.rotate(Axis::X, 90.0);
-        println!("{:?}", vec);
-        assert_eq!(1, 1);
+        Matrix4::new(
+            f / aspect, 0.0, 0.0, 0.0,
+            0.0, f, 0.0, 0.0,
+            0.0, 0.0, 0.0, -1.0,
+            0.0, 0.0, near, 0.0,
+        )
+    }
+
+    // Same as glFrustum
+    #[rustfmt::skip]
+    pub fn frustum(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Matrix4 {
+        Matrix4::new(
+            (2.0 * near) / (right - left), 0.0, 0.0, 0.0,
+            0.0, (2.0 * near) / (top - bottom), 0.0, 0.0,
+            (right + left) / (right - left), (top + bottom) / (top - bottom), -(far + near) / (far - near), -1.0,
+            0.0, 0.0, -(2.0 * far * near) / (far - near), 0.0,
+        )
+    }
+
+    // Same as glOrtho
+    #[rustfmt::skip]
+    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Matrix4 {
+        Matrix4::new(
+            2.0 / (right - left), 0.0, 0.0, 0.0,
+            0.0, 2.0 / (top - bottom), 0.0, 0.0,
+            0.0, 0.0, -2.0 / (far - near), 0.0,
+            -(right + left) / (right - left), -(top + bottom) / (top - bottom), -(far + near) / (far - near), 1.0,
+        )
+    }
+}
+
+impl Matrix4 {
+    // Transforms every point in place as (x, y, z, 1), the resulting w is dropped.
+    // Uses SIMD where available, see simd.rs.
+    pub fn transform_points(&self, points: &mut [Vector3]) {
+        simd::transform_points(self, points);
+    }
+
+    // Transforms every vector in place, same as v = self * v for each of them
+    pub fn transform_vectors(&self, vectors: &mut [Vector4]) {
+        simd::transform_vectors(self, vectors);
+    }
+}
+
+// ###########################  VECTOR METHODS  ###########################################################
+// Only for Vector2 and Vector3 style vectors. Vector4 keeps its own xyz-only normalize/get_length.
+macro_rules! impl_vector_methods {
+    ($N:literal) => {
+        #[allow(dead_code)]
+        impl<T: Float> Vector<T, $N> {
+            pub fn length(self) -> T {
+                self.length_squared().sqrt()
+            }
+
+            pub fn normalize(&mut self) {
+                *self = self.normalized();
+            }
+
+            pub fn normalized(self) -> Self {
+                self / self.length()
+            }
+
+            pub fn distance(self, other: Self) -> T {
+                (other - self).length()
+            }
+
+            // t = 0 gives self, t = 1 gives other
+            pub fn lerp(self, other: Self, t: T) -> Self {
+                self + (other - self) * t
+            }
+
+            // @params: normal must be normalized
+            pub fn reflect(self, normal: Self) -> Self {
+                self - normal * ((T::ONE + T::ONE) * self.dot(normal))
+            }
+
+            // Same as GLSL refract(), returns zero on total internal reflection
+            // @params: self and normal must be normalized, eta is the ratio of indices of refraction
+            pub fn refract(self, normal: Self, eta: T) -> Self {
+                let n_dot_i = normal.dot(self);
+                let k = T::ONE - eta * eta * (T::ONE - n_dot_i * n_dot_i);
+
+                if k < T::ZERO {
+                    Self::zero()
+                } else {
+                    self * eta - normal * (eta * n_dot_i + k.sqrt())
+                }
+            }
+
+            // Projection of self onto other
+            pub fn project(self, other: Self) -> Self {
+                other * (self.dot(other) / other.length_squared())
+            }
+
+            pub fn abs(self) -> Self {
+                self.map(T::abs)
+            }
+        }
+    };
+}
+
+impl_vector_methods! { 2 }
+impl_vector_methods! { 3 }
+
+// ###########################  OPERATORS  ################################################################
+// Vectors get component-wise + - * /, scalar * and /, negation and indexing.
+impl<T: Scalar, const N: usize> Add for Vector<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.zip_map(rhs, |a, b| a + b)
+    }
+}
+
+impl<T: Scalar, const N: usize> Sub for Vector<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.zip_map(rhs, |a, b| a - b)
+    }
+}
+
+impl<T: Scalar, const N: usize> Mul<T> for Vector<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        self.map(|a| a * rhs)
+    }
+}
+
+impl<T: Scalar, const N: usize> Div<T> for Vector<T, N> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        self.map(|a| a / rhs)
+    }
+}
+
+// Component-wise
+impl<T: Scalar, const N: usize> Mul for Vector<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.zip_map(rhs, |a, b| a * b)
+    }
+}
+
+// Component-wise
+impl<T: Scalar, const N: usize> Div for Vector<T, N> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        self.zip_map(rhs, |a, b| a / b)
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>, const N: usize> Neg for Vector<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|a| -a)
+    }
+}
+
+impl<T: Scalar, const N: usize> AddAssign for Vector<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Scalar, const N: usize> SubAssign for Vector<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Scalar, const N: usize> MulAssign<T> for Vector<T, N> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Scalar, const N: usize> DivAssign<T> for Vector<T, N> {
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T: Scalar, const N: usize> MulAssign for Vector<T, N> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Scalar, const N: usize> DivAssign for Vector<T, N> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        match self.0.get(i) {
+            Some(n) => n,
+            None => panic!("Index provided to Vector is out of bounds!"),
+        }
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Vector<T, N> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        match self.0.get_mut(i) {
+            Some(n) => n,
+            None => panic!("Index provided to Vector is out of bounds!"),
+        }
+    }
+}
+
+// Matrices are stored as columns, so indexing returns a column and
+// matrix * vector is a sum of the columns scaled by the vector's components.
+impl<T: Scalar, const R: usize, const C: usize> Add for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Matrix(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Sub for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Matrix(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Mul<T> for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        self.map(|a| a * rhs)
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Div<T> for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        self.map(|a| a / rhs)
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>, const R: usize, const C: usize> Neg for Matrix<T, R, C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|a| -a)
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Mul<Vector<T, C>> for Matrix<T, R, C> {
+    type Output = Vector<T, R>;
+
+    fn mul(self, rhs: Vector<T, C>) -> Vector<T, R> {
+        if R == 4 && C == 4 {
+            // SAFETY: R and C were just checked, so these are the same types
+            unsafe {
+                let m = &*(&self as *const Self as *const Matrix<T, 4, 4>);
+                let v = *(&rhs as *const Vector<T, C> as *const Vector<T, 4>);
+                std::mem::transmute_copy(&T::mul_matrix4_vector(m, v))
+            }
+        } else {
+            matrix_mul_vector(&self, rhs)
+        }
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>>
+    for Matrix<T, R, C>
+{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: Matrix<T, C, K>) -> Matrix<T, R, K> {
+        if R == 4 && C == 4 && K == 4 {
+            // SAFETY: R, C and K were just checked, so these are the same types
+            unsafe {
+                let a = &*(&self as *const Self as *const Matrix<T, 4, 4>);
+                let b = &*(&rhs as *const Matrix<T, C, K> as *const Matrix<T, 4, 4>);
+                std::mem::transmute_copy(&T::mul_matrix4(a, b))
+            }
+        } else {
+            matrix_mul_matrix(&self, &rhs)
+        }
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> AddAssign for Matrix<T, R, C> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> SubAssign for Matrix<T, R, C> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Scalar, const N: usize> MulAssign for Matrix<T, N, N> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> MulAssign<T> for Matrix<T, R, C> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> DivAssign<T> for Matrix<T, R, C> {
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<usize> for Matrix<T, R, C> {
+    type Output = Vector<T, R>;
+
+    fn index(&self, i: usize) -> &Vector<T, R> {
+        match self.0.get(i) {
+            Some(column) => column,
+            None => panic!("Index provided to Matrix is out of bounds!"),
+        }
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<usize> for Matrix<T, R, C> {
+    fn index_mut(&mut self, i: usize) -> &mut Vector<T, R> {
+        match self.0.get_mut(i) {
+            Some(column) => column,
+            None => panic!("Index provided to Matrix is out of bounds!"),
+        }
+    }
+}
+
+// scalar * vector and scalar * matrix, these can't be generic over T
+macro_rules! impl_scalar_lhs_ops {
+    ($($t:ident),+) => {
+        $(
+            impl<const N: usize> Mul<Vector<$t, N>> for $t {
+                type Output = Vector<$t, N>;
+
+                fn mul(self, rhs: Vector<$t, N>) -> Vector<$t, N> {
+                    rhs * self
+                }
+            }
+
+            impl<const R: usize, const C: usize> Mul<Matrix<$t, R, C>> for $t {
+                type Output = Matrix<$t, R, C>;
+
+                fn mul(self, rhs: Matrix<$t, R, C>) -> Matrix<$t, R, C> {
+                    rhs * self
+                }
+            }
+        )+
+    };
+}
+
+impl_scalar_lhs_ops! { f32, f64, i32, u32 }
+
+// Plain matrix products that the operators use, Matrix4 goes through Scalar so f32 can use SIMD.
+// Sums the columns scaled by the vector's components, in order.
+pub(crate) fn matrix_mul_vector<T: Scalar, const R: usize, const C: usize>(
+    m: &Matrix<T, R, C>,
+    v: Vector<T, C>,
+) -> Vector<T, R> {
+    let mut scaled = m.0.iter().zip(v.0.iter()).map(|(column, n)| *column * *n);
+
+    match scaled.next() {
+        Some(first) => scaled.fold(first, |sum, column| sum + column),
+        None => Vector::zero(),
+    }
+}
+
+pub(crate) fn matrix_mul_matrix<T: Scalar, const R: usize, const C: usize, const K: usize>(
+    a: &Matrix<T, R, C>,
+    b: &Matrix<T, C, K>,
+) -> Matrix<T, R, K> {
+    Matrix(b.0.map(|column| matrix_mul_vector(a, column)))
+}
+
+// 2x2 determinants of the top two and bottom two rows, shared by Matrix4::determinant and inverse
+fn sub_determinants<T: Scalar>(m: &[[T; 4]; 4]) -> ([T; 6], [T; 6]) {
+    let s = [
+        m[0][0] * m[1][1] - m[1][0] * m[0][1],
+        m[0][0] * m[1][2] - m[1][0] * m[0][2],
+        m[0][0] * m[1][3] - m[1][0] * m[0][3],
+        m[0][1] * m[1][2] - m[1][1] * m[0][2],
+        m[0][1] * m[1][3] - m[1][1] * m[0][3],
+        m[0][2] * m[1][3] - m[1][2] * m[0][3],
+    ];
+
+    let c = [
+        m[2][0] * m[3][1] - m[3][0] * m[2][1],
+        m[2][0] * m[3][2] - m[3][0] * m[2][2],
+        m[2][0] * m[3][3] - m[3][0] * m[2][3],
+        m[2][1] * m[3][2] - m[3][1] * m[2][2],
+        m[2][1] * m[3][3] - m[3][1] * m[2][3],
+        m[2][2] * m[3][3] - m[3][2] * m[2][3],
+    ];
+
+    (s, c)
+}
+
+fn multiply_col_with_row<T: Scalar>(col: [T; 4], row: [T; 4]) -> T {
+    let mut sum = T::ZERO;
+
+    for i in 0..col.len() {
+        sum += col[i] * row[i];
+    }
+
+    sum
+}
+
+pub fn convert_to_radians(angle: f32) -> f32 {
+    angle * (std::f32::consts::PI / 180.0)
+}
+
+pub fn convert_to_degrees(radians: f32) -> f32 {
+    radians * (180.0 / std::f32::consts::PI)
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod vector_tests {
+    use crate::assert_abs_diff_eq;
+    use crate::math::Axis;
+    use crate::math::Deg;
+    use crate::math::Vector2;
+    use crate::math::Vector3;
+    use crate::math::Vector4;
+
+    use super::Matrix4;
+    #[test]
+    fn test_get_index() {
+        let vec = Vector4::new(0.0, 1.0, 2.0, 3.0);
+
+        assert_eq!(0.0, vec.get_index(0));
+        assert_eq!(1.0, vec.get_index(1));
+        assert_eq!(2.0, vec.get_index(2));
+        assert_eq!(3.0, vec.get_index(3));
+    }
+
+    #[test]
+    fn test_as_array() {
+        let vec = Vector4::new(0.9, 0.9, 0.9, 0.9);
+        let vec_arr = vec.as_array();
+
+        for i in vec_arr {
+            assert_eq!(i, 0.9);
+        }
+    }
+
+    #[test]
+    fn test_add_scalar() {
+        let mut vec = Vector4::new(0.5, 0.5, 0.6, 0.2);
+        vec.add_scalar(2.0);
+
+        let vec = vec.as_array();
+
+        assert_eq!(vec[0], 2.5);
+        assert_eq!(vec[1], 2.5);
+        assert_eq!(vec[2], 2.6);
+        assert_eq!(vec[3], 2.2);
+    }
+
+    #[test]
+    fn test_subtract_scalar() {
+        let mut vec = Vector4::new(1.0, 1.0, 1.0, 1.0);
+        vec.subtract_scalar(2.0);
+
+        let vec = vec.as_array();
+
+        for i in vec {
+            assert_eq!(i, -1.0);
+        }
+    }
+
+    #[test]
+    fn test_multiply_scalar() {
+        let mut vec = Vector4::new(2.0, 2.0, 2.0, 2.0);
+        vec.multiply_scalar(0.5);
+
+        let vec = vec.as_array();
+
+        for i in vec {
+            assert_eq!(i, 1.0);
+        }
+    }
+
+    #[test]
+    fn test_divide_scalar() {
+        let mut vec = Vector4::new(3.0, 3.0, 3.0, 3.0);
+        vec.divide_scalar(2.0);
+
+        let vec = vec.as_array();
+
+        for i in vec {
+            assert_eq!(i, 1.5);
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut vec = Vector4::new(3.0, 5.0, 7.0, 1.0);
+        vec.normalize();
+
+        assert_abs_diff_eq!(vec.xyz(), Vector3::new(0.329293, 0.548821, 0.76835), 1e-5);
+    }
+
+    #[test]
+    fn test_get_length() {
+        let vec = Vector4::new(5.0, 7.0, 3.0, 1.0);
+        let length = vec.get_length();
+
+        assert_abs_diff_eq!(length, 9.11043, 1e-5);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut vec = Vector4::new(5.0, 5.0, 9.0, 1.0);
+
+        // y goes to z and z to -y
+        vec.rotate(Axis::X, Deg(90.0));
+        assert_abs_diff_eq!(vec, Vector4::new(5.0, -9.0, 5.0, 1.0), 1e-5);
     }
 
     #[test]
     fn test_create_vec_and_translate() {
         let mut vec = Vector4::new(1.0, 0.0, 0.0, 1.0);
 
-        let trans = Matrix4::create_translation(Matrix4::identity(), Vector3::new(1.0, 1.0, 0.0));
+        let trans = Matrix4::create_translation(Matrix4::identity(), Vector3::new(1.0, 1.0, 0.0));
+
+        vec.translate(trans);
+
+        println!("{:?}", vec);
+
+        assert_eq!(vec.x, 2.0);
+        assert_eq!(vec.y, 1.0);
+        assert_eq!(vec.z, 0.0);
+        assert_eq!(vec.w, 1.0);
+    }
+
+    #[test]
+    fn test_dot_and_cross() {
+        let x = Vector3::new(1.0, 0.0, 0.0);
+        let y = Vector3::new(0.0, 1.0, 0.0);
+
+        assert_eq!(x.dot(y), 0.0);
+        assert_eq!(
+            Vector3::new(1.0, 2.0, 3.0).dot(Vector3::new(4.0, 5.0, 6.0)),
+            32.0
+        );
+        assert_eq!(x.cross(y), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(y.cross(x), Vector3::new(0.0, 0.0, -1.0));
+
+        assert_eq!(Vector2::new(1.0, 0.0).cross(Vector2::new(0.0, 1.0)), 1.0);
+        assert_eq!(Vector2::new(1.0, 0.0).perp(), Vector2::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn test_length_and_normalize() {
+        let mut vec = Vector3::new(3.0, 0.0, 4.0);
+
+        assert_eq!(vec.length_squared(), 25.0);
+        assert_eq!(vec.length(), 5.0);
+        assert_eq!(vec.normalized(), Vector3::new(0.6, 0.0, 0.8));
+
+        vec.normalize();
+        assert_eq!(vec, Vector3::new(0.6, 0.0, 0.8));
+
+        assert_eq!(Vector2::new(3.0, 4.0).length(), 5.0);
+        assert_eq!(Vector2::new(1.0, 1.0).distance(Vector2::new(4.0, 5.0)), 5.0);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let a = Vector3::new(0.0, 0.0, 0.0);
+        let b = Vector3::new(2.0, 4.0, -8.0);
+
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.5), Vector3::new(1.0, 2.0, -4.0));
+    }
+
+    #[test]
+    fn test_reflect() {
+        let incoming = Vector3::new(1.0, -1.0, 0.0);
+        let normal = Vector3::new(0.0, 1.0, 0.0);
+
+        assert_eq!(incoming.reflect(normal), Vector3::new(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_refract() {
+        let normal = Vector3::new(0.0, 1.0, 0.0);
+        let straight_down = Vector3::new(0.0, -1.0, 0.0);
+
+        // Head-on rays pass straight through
+        assert_eq!(straight_down.refract(normal, 1.0 / 1.33), straight_down);
+
+        // Grazing ray leaving a denser medium is totally internally reflected
+        let grazing = Vector3::new(1.0, -0.1, 0.0).normalized();
+        assert_eq!(grazing.refract(normal, 1.5), Vector3::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_project() {
+        let v = Vector2::new(3.0, 4.0);
+
+        assert_eq!(v.project(Vector2::new(2.0, 0.0)), Vector2::new(3.0, 0.0));
+    }
+
+    #[test]
+    fn test_min_max_clamp() {
+        let a = Vector3::new(1.0, 5.0, -3.0);
+        let b = Vector3::new(2.0, 0.0, -4.0);
+
+        assert_eq!(a.min(b), Vector3::new(1.0, 0.0, -4.0));
+        assert_eq!(a.max(b), Vector3::new(2.0, 5.0, -3.0));
+        assert_eq!(
+            a.clamp(Vector3::splat(0.0), Vector3::splat(2.0)),
+            Vector3::new(1.0, 2.0, 0.0)
+        );
+        assert_eq!(a.abs(), Vector3::new(1.0, 5.0, 3.0));
+    }
+
+    #[test]
+    fn test_component_wise_ops() {
+        let a = Vector3::new(1.0, 2.0, 3.0);
+        let b = Vector3::new(2.0, 4.0, 6.0);
+
+        assert_eq!(a * b, Vector3::new(2.0, 8.0, 18.0));
+        assert_eq!(b / a, Vector3::new(2.0, 2.0, 2.0));
+
+        let mut c = a;
+        c *= b;
+        c /= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn test_swizzle_extend_truncate() {
+        let v4 = Vector4::new(1.0, 2.0, 3.0, 4.0);
 
-        vec.translate(trans);
+        assert_eq!(v4.xy(), Vector2::new(1.0, 2.0));
+        assert_eq!(v4.xyz(), Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(v4.truncate(), Vector3::new(1.0, 2.0, 3.0));
 
-        println!("{:?}", vec);
+        let v3 = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(v3.xy(), Vector2::new(1.0, 2.0));
+        assert_eq!(v3.truncate(), Vector2::new(1.0, 2.0));
+        assert_eq!(v3.extend(4.0), v4);
 
-        assert_eq!(vec.x, 2.0);
-        assert_eq!(vec.y, 1.0);
-        assert_eq!(vec.z, 0.0);
-        assert_eq!(vec.w, 1.0);
+        assert_eq!(Vector2::new(1.0, 2.0).extend(3.0), v3);
     }
 }
 
@@ -530,6 +1533,7 @@ mod vector_tests {
 #[cfg(test)]
 mod matrix_tests {
     use super::Matrix4;
+    use crate::assert_abs_diff_eq;
     use crate::math::*;
 
     #[test]
@@ -543,7 +1547,7 @@ mod matrix_tests {
     }
 
     #[test]
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     fn test_matrix4_getrow() {
         let mat = Matrix4::new(
             1.0, 2.0, 3.0, 4.0, 
@@ -562,7 +1566,7 @@ mod matrix_tests {
     }
 
     #[test]
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     fn test_matrix4_getcolumn(){
         let mat = Matrix4::new(
             1.0, 2.0, 3.0, 4.0, 
@@ -591,7 +1595,7 @@ mod matrix_tests {
     }
 
     #[test]
-    #[cfg_attr(rustfmt, rustfmt_skip)]
+    #[rustfmt::skip]
     fn test_matrix_translate() {
         let mut m1 = Matrix4::new(
             2.0, 1.0, 6.0, 1.0,
@@ -629,22 +1633,660 @@ mod matrix_tests {
 
     }
 
+    #[rustfmt::skip]
+    fn sample_matrix4() -> Matrix4 {
+        Matrix4::new(
+            1.0, 0.0, 2.0, -1.0,
+            3.0, 0.0, 0.0, 5.0,
+            2.0, 1.0, 4.0, -3.0,
+            1.0, 0.0, 5.0, 0.0,
+        )
+    }
+
+    #[test]
+    fn test_matrix4_transpose() {
+        let mat = sample_matrix4();
+
+        assert_eq!(mat.transpose().get_row(0), mat.get_column(0));
+        assert_eq!(mat.transpose().get_column(3), mat.get_row(3));
+        assert_eq!(mat.transpose().transpose(), mat);
+    }
+
+    #[test]
+    fn test_matrix4_determinant() {
+        assert_eq!(sample_matrix4().determinant(), 30.0);
+        assert_eq!(sample_matrix4().transpose().determinant(), 30.0);
+        assert_eq!(Matrix4::identity().determinant(), 1.0);
+    }
+
+    #[test]
+    fn test_matrix4_inverse() {
+        let mat = sample_matrix4();
+        let inverse = mat.inverse().unwrap();
+
+        assert_abs_diff_eq!(mat * inverse, Matrix4::identity(), 1e-5);
+        assert_abs_diff_eq!(inverse * mat, Matrix4::identity(), 1e-5);
+    }
+
+    #[test]
+    fn test_matrix4_inverse_singular() {
+        let mut mat = sample_matrix4();
+        mat.w = mat.x;
+
+        assert_eq!(mat.inverse(), None);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_matrix4_inverse_affine() {
+        let mut rotation = Matrix4::identity();
+        rotation.x.rotate(Axis::Z, Rad(0.5));
+        rotation.y.rotate(Axis::Z, Rad(0.5));
+
+        let scale = Matrix4::new(
+            2.0, 0.0, 0.0, 0.0,
+            0.0, 3.0, 0.0, 0.0,
+            0.0, 0.0, 4.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        );
+        let translation = Matrix4::create_translation(Matrix4::identity(), Vector3::new(1.0, -2.0, 3.0));
+
+        let model = translation * rotation * scale;
+
+        assert_abs_diff_eq!(model.inverse_affine().unwrap(), model.inverse().unwrap(), 1e-5);
+        assert_abs_diff_eq!(model * model.inverse_affine().unwrap(), Matrix4::identity(), 1e-5);
+    }
+
+    #[test]
+    fn test_matrix3_inverse() {
+        let mat = Matrix3::new(2.0, 0.0, 0.0, 1.0, 3.0, 0.0, 4.0, 5.0, 6.0);
+
+        assert_eq!(mat.determinant(), 36.0);
+
+        assert_abs_diff_eq!(mat * mat.inverse().unwrap(), Matrix3::identity(), 1e-6);
+
+        assert_eq!(
+            Matrix3::new(1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 0.0, 0.0, 1.0).inverse(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_matrix3_transpose() {
+        let mat = Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+
+        assert_eq!(
+            mat.transpose(),
+            Matrix3::new(1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0)
+        );
+    }
+
+    #[test]
+    fn test_matrix2_inverse() {
+        let mat = Matrix2::new(4.0, 2.0, 7.0, 6.0);
+
+        assert_eq!(mat.determinant(), 10.0);
+        assert_eq!(mat.transpose(), Matrix2::new(4.0, 7.0, 2.0, 6.0));
+        assert_eq!(mat.inverse().unwrap(), Matrix2::new(0.6, -0.2, -0.7, 0.4));
+        assert_eq!(Matrix2::new(1.0, 2.0, 2.0, 4.0).inverse(), None);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_normal_matrix() {
+        let model = Matrix4::new(
+            2.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            5.0, 6.0, 7.0, 1.0,
+        );
+
+        let normal = Matrix3::normal_matrix(model).unwrap();
+
+        assert_eq!(normal, Matrix3::new(0.5, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0));
+    }
+
     #[test]
     fn test_multiply_colrow() {
         let col: [f32; 4] = [4.0, 5.0, 3.5, 0.7];
         let row: [f32; 4] = [1.3, 1.2, 0.7, 0.4];
 
-        let res = (multiply_col_with_row(col, row) * 100.0).round() / 100.0; // rounded to two decimal places
+        assert_abs_diff_eq!(multiply_col_with_row(col, row), 13.93, 1e-5);
+    }
+}
+
+#[cfg(test)]
+mod ops_tests {
+    use crate::math::*;
+
+    #[test]
+    fn test_vector_add_sub() {
+        let a = Vector3::new(1.0, 2.0, 3.0);
+        let b = Vector3::new(4.0, 5.0, 6.0);
+
+        assert_eq!(a + b, Vector3::new(5.0, 7.0, 9.0));
+        assert_eq!(b - a, Vector3::new(3.0, 3.0, 3.0));
+        assert_eq!(-a, Vector3::new(-1.0, -2.0, -3.0));
+    }
+
+    #[test]
+    fn test_vector_scalar() {
+        let v = Vector2::new(2.0, -4.0);
+
+        assert_eq!(v * 2.0, Vector2::new(4.0, -8.0));
+        assert_eq!(2.0 * v, Vector2::new(4.0, -8.0));
+        assert_eq!(v / 2.0, Vector2::new(1.0, -2.0));
+    }
+
+    #[test]
+    fn test_vector_assign_ops() {
+        let mut v = Vector4::new(1.0, 1.0, 1.0, 1.0);
+        v += Vector4::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(v, Vector4::new(2.0, 3.0, 4.0, 5.0));
+
+        v -= Vector4::new(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(v, Vector4::new(1.0, 2.0, 3.0, 4.0));
+
+        v *= 2.0;
+        assert_eq!(v, Vector4::new(2.0, 4.0, 6.0, 8.0));
+
+        v /= 2.0;
+        assert_eq!(v, Vector4::new(1.0, 2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn test_vector_index() {
+        let mut v = Vector4::new(0.0, 1.0, 2.0, 3.0);
+
+        for i in 0..4 {
+            assert_eq!(v[i], i as f32);
+        }
+
+        v[2] = 7.0;
+        assert_eq!(v.z, 7.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_vector_index_panic() {
+        let v = Vector3::new(0.0, 1.0, 2.0);
+
+        let _ = v[3];
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_matrix_mul_matches_multiply_matrix() {
+        let m1 = Matrix4::new(
+            2.0, 1.0, 6.0, 1.0,
+            3.0, 2.0, 7.0, 2.0,
+            4.0, 3.0, 8.0, 3.0,
+            5.0, 4.0, 9.0, 4.0
+        );
+
+        let m2 = Matrix4::new(
+            5.0, 5.0, 6.0, 6.0,
+            5.0, 5.0, 6.0, 6.0,
+            5.0, 5.0, 6.0, 6.0,
+            5.0, 5.0, 6.0, 6.0
+        );
+
+        let mut expected = m1;
+        expected.multiply_matrix(m2);
+
+        assert_eq!(m1 * m2, expected);
+
+        let mut m3 = m1;
+        m3 *= m2;
+        assert_eq!(m3, expected);
+    }
+
+    #[test]
+    fn test_matrix_mul_vector() {
+        let trans = Matrix4::create_translation(Matrix4::identity(), Vector3::new(1.0, 2.0, 3.0));
+        let v = trans * Vector4::new(1.0, 1.0, 1.0, 1.0);
+
+        assert_eq!(v, Vector4::new(2.0, 3.0, 4.0, 1.0));
+
+        let m = Matrix2::new(0.0, 1.0, -1.0, 0.0);
+        assert_eq!(m * Vector2::new(1.0, 0.0), Vector2::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn test_matrix_identity_mul() {
+        let m = Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+
+        assert_eq!(m * Matrix3::identity(), m);
+        assert_eq!(Matrix3::identity() * m, m);
+    }
+
+    #[test]
+    fn test_matrix_scalar_and_add() {
+        let m = Matrix2::identity();
+
+        assert_eq!(m * 3.0, Matrix2::new(3.0, 0.0, 0.0, 3.0));
+        assert_eq!(3.0 * m, Matrix2::new(3.0, 0.0, 0.0, 3.0));
+        assert_eq!((m * 3.0) / 3.0, m);
+        assert_eq!(m + m, Matrix2::new(2.0, 0.0, 0.0, 2.0));
+        assert_eq!(m - m, Matrix2::new(0.0, 0.0, 0.0, 0.0));
+        assert_eq!(-m, Matrix2::new(-1.0, 0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_matrix_index() {
+        let mut m = Matrix4::identity();
+
+        assert_eq!(m[3], Vector4::new(0.0, 0.0, 0.0, 1.0));
+
+        m[3][0] = 5.0;
+        assert_eq!(m.w.x, 5.0);
+    }
+}
+
+#[cfg(test)]
+mod projection_tests {
+    use crate::assert_abs_diff_eq;
+    use crate::math::*;
+
+    // Clip space to NDC
+    fn project(proj: Matrix4, point: Vector3) -> Vector3 {
+        let clip = proj * point.extend(1.0);
+        clip.xyz() / clip.w
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_perspective_reference() {
+        // Values from glm::perspective(glm::radians(45.0f), 4.0f / 3.0f, 0.1f, 100.0f)
+        let proj = Matrix4::perspective(Deg(45.0), 4.0 / 3.0, 0.1, 100.0);
+
+        assert_abs_diff_eq!(
+            proj,
+            Matrix4::new(
+                1.8106601, 0.0, 0.0, 0.0,
+                0.0, 2.4142136, 0.0, 0.0,
+                0.0, 0.0, -1.002002, -1.0,
+                0.0, 0.0, -0.2002002, 0.0,
+            ), 1e-5);
+    }
+
+    #[test]
+    fn test_perspective_depth_range() {
+        let proj = Matrix4::perspective(Deg(60.0), 1.0, 0.5, 50.0);
+
+        assert_abs_diff_eq!(project(proj, Vector3::new(0.0, 0.0, -0.5)).z, -1.0, 1e-5);
+        assert_abs_diff_eq!(project(proj, Vector3::new(0.0, 0.0, -50.0)).z, 1.0, 1e-5);
+    }
+
+    #[test]
+    fn test_perspective_matches_frustum() {
+        let fov_y = Rad::from(Deg(70.0));
+        let aspect = 16.0 / 9.0;
+        let top = 0.1 * (fov_y / 2.0).tan();
+        let right = top * aspect;
+
+        assert_abs_diff_eq!(
+            Matrix4::perspective(fov_y, aspect, 0.1, 100.0),
+            Matrix4::frustum(-right, right, -top, top, 0.1, 100.0),
+            1e-5
+        );
+    }
+
+    #[test]
+    fn test_frustum_corners() {
+        let proj = Matrix4::frustum(-1.0, 2.0, -0.5, 1.5, 1.0, 10.0);
+
+        let near_corner = project(proj, Vector3::new(-1.0, -0.5, -1.0));
+        assert_abs_diff_eq!(near_corner.x, -1.0, 1e-5);
+        assert_abs_diff_eq!(near_corner.y, -1.0, 1e-5);
+        assert_abs_diff_eq!(near_corner.z, -1.0, 1e-5);
+
+        let far_corner = project(proj, Vector3::new(20.0, 15.0, -10.0));
+        assert_abs_diff_eq!(far_corner.x, 1.0, 1e-5);
+        assert_abs_diff_eq!(far_corner.y, 1.0, 1e-5);
+        assert_abs_diff_eq!(far_corner.z, 1.0, 1e-5);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_orthographic_reference() {
+        // Values from glm::ortho(0.0f, 800.0f, 0.0f, 600.0f, -1.0f, 1.0f)
+        let proj = Matrix4::orthographic(0.0, 800.0, 0.0, 600.0, -1.0, 1.0);
+
+        assert_abs_diff_eq!(
+            proj,
+            Matrix4::new(
+                0.0025, 0.0, 0.0, 0.0,
+                0.0, 0.0033333334, 0.0, 0.0,
+                0.0, 0.0, -1.0, 0.0,
+                -1.0, -1.0, 0.0, 1.0,
+            ), 1e-5);
+
+        let corner = project(proj, Vector3::new(800.0, 600.0, -1.0));
+        assert_abs_diff_eq!(corner.x, 1.0, 1e-5);
+        assert_abs_diff_eq!(corner.y, 1.0, 1e-5);
+        assert_abs_diff_eq!(corner.z, 1.0, 1e-5);
+    }
+
+    #[test]
+    fn test_perspective_infinite() {
+        let proj = Matrix4::perspective_infinite(Deg(90.0), 1.0, 0.1);
+
+        assert_abs_diff_eq!(project(proj, Vector3::new(0.0, 0.0, -0.1)).z, -1.0, 1e-5);
+
+        let far = project(proj, Vector3::new(0.0, 0.0, -1.0e6)).z;
+        assert!(far < 1.0 && far > 0.9999);
+    }
+
+    #[test]
+    fn test_perspective_reversed_z() {
+        let proj = Matrix4::perspective_reversed_z(Deg(90.0), 1.0, 0.1, 100.0);
+
+        assert_abs_diff_eq!(project(proj, Vector3::new(0.0, 0.0, -0.1)).z, 1.0, 1e-5);
+        assert_abs_diff_eq!(project(proj, Vector3::new(0.0, 0.0, -100.0)).z, 0.0, 1e-5);
+
+        // Depth gets smaller as things move away
+        let mid = project(proj, Vector3::new(0.0, 0.0, -10.0)).z;
+        assert!(mid > 0.0 && mid < 1.0);
+    }
+
+    #[test]
+    fn test_perspective_infinite_reversed_z() {
+        let proj = Matrix4::perspective_infinite_reversed_z(Deg(90.0), 1.0, 0.1);
+
+        assert_abs_diff_eq!(project(proj, Vector3::new(0.0, 0.0, -0.1)).z, 1.0, 1e-5);
+
+        let far = project(proj, Vector3::new(0.0, 0.0, -1.0e6)).z;
+        assert!(far > 0.0 && far < 1.0e-6);
+    }
+}
+
+#[cfg(test)]
+mod view_tests {
+    use crate::assert_abs_diff_eq;
+    use crate::math::*;
+
+    #[test]
+    fn test_look_at_rh_identity() {
+        // Camera at the origin looking down -z is the identity view
+        let view = Matrix4::look_at_rh(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.0, -1.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+
+        assert_eq!(view, Matrix4::identity());
+    }
+
+    #[test]
+    fn test_look_at_lh_identity() {
+        let view = Matrix4::look_at_lh(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+
+        assert_eq!(view, Matrix4::identity());
+    }
+
+    #[test]
+    fn test_look_at_rh_moves_target_in_front() {
+        let eye = Vector3::new(3.0, 4.0, 5.0);
+        let target = Vector3::new(-1.0, 2.0, 0.5);
+        let view = Matrix4::look_at_rh(eye, target, Vector3::new(0.0, 1.0, 0.0));
+
+        assert_abs_diff_eq!(
+            (view * eye.extend(1.0)).xyz(),
+            Vector3::new(0.0, 0.0, 0.0),
+            1e-5
+        );
+
+        let distance = eye.distance(target);
+        assert_abs_diff_eq!(
+            (view * target.extend(1.0)).xyz(),
+            Vector3::new(0.0, 0.0, -distance),
+            1e-5
+        );
+    }
+
+    #[test]
+    fn test_look_at_lh_moves_target_in_front() {
+        let eye = Vector3::new(3.0, 4.0, 5.0);
+        let target = Vector3::new(-1.0, 2.0, 0.5);
+        let view = Matrix4::look_at_lh(eye, target, Vector3::new(0.0, 1.0, 0.0));
+
+        let distance = eye.distance(target);
+        assert_abs_diff_eq!(
+            (view * target.extend(1.0)).xyz(),
+            Vector3::new(0.0, 0.0, distance),
+            1e-5
+        );
+    }
+
+    #[test]
+    fn test_view_basis_rh() {
+        let eye = Vector3::new(0.0, 0.0, 5.0);
+        let view = Matrix4::look_at_rh(
+            eye,
+            Vector3::new(5.0, 0.0, 5.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+        let (right, up, forward) = view.view_basis_rh();
+
+        assert_abs_diff_eq!(forward, Vector3::new(1.0, 0.0, 0.0), 1e-5);
+        assert_abs_diff_eq!(up, Vector3::new(0.0, 1.0, 0.0), 1e-5);
+        assert_abs_diff_eq!(right, Vector3::new(0.0, 0.0, 1.0), 1e-5);
+    }
+
+    #[test]
+    fn test_view_basis_lh() {
+        let eye = Vector3::new(0.0, 0.0, 5.0);
+        let view = Matrix4::look_at_lh(
+            eye,
+            Vector3::new(5.0, 0.0, 5.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+        let (right, up, forward) = view.view_basis_lh();
+
+        assert_abs_diff_eq!(forward, Vector3::new(1.0, 0.0, 0.0), 1e-5);
+        assert_abs_diff_eq!(up, Vector3::new(0.0, 1.0, 0.0), 1e-5);
+        assert_abs_diff_eq!(right, Vector3::new(0.0, 0.0, -1.0), 1e-5);
+    }
+}
+
+#[cfg(test)]
+mod raw_access_tests {
+    use crate::math::*;
+
+    #[test]
+    fn test_layout_sizes() {
+        assert_eq!(std::mem::size_of::<Vector2>(), 2 * 4);
+        assert_eq!(std::mem::size_of::<Vector3>(), 3 * 4);
+        assert_eq!(std::mem::size_of::<Vector4>(), 4 * 4);
+        assert_eq!(std::mem::size_of::<Matrix2>(), 4 * 4);
+        assert_eq!(std::mem::size_of::<Matrix3>(), 9 * 4);
+        assert_eq!(std::mem::size_of::<Matrix4>(), 16 * 4);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_matrix4_as_slice_is_column_major() {
+        let mat = Matrix4::new(
+            0.0, 1.0, 2.0, 3.0,
+            4.0, 5.0, 6.0, 7.0,
+            8.0, 9.0, 10.0, 11.0,
+            12.0, 13.0, 14.0, 15.0,
+        );
+
+        let slice = mat.as_slice();
+
+        assert_eq!(slice.len(), 16);
+        for (i, value) in slice.iter().enumerate() {
+            assert_eq!(*value, i as f32);
+        }
+
+        // Translation lives in elements 12, 13, 14
+        let trans = Matrix4::create_translation(Matrix4::identity(), Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(&trans.as_slice()[12..15], &[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_as_ptr_reads_the_matrix() {
+        let mat = Matrix4::create_translation(Matrix4::identity(), Vector3::new(7.0, 8.0, 9.0));
+        let ptr = mat.as_ptr();
+
+        let read = unsafe { std::slice::from_raw_parts(ptr, 16) };
+        assert_eq!(read, mat.as_slice());
+        assert_eq!(read[12], 7.0);
+    }
+
+    #[test]
+    fn test_vector_as_slice() {
+        assert_eq!(Vector2::new(1.0, 2.0).as_slice(), &[1.0, 2.0]);
+        assert_eq!(Vector3::new(1.0, 2.0, 3.0).as_slice(), &[1.0, 2.0, 3.0]);
+        assert_eq!(
+            Vector4::new(1.0, 2.0, 3.0, 4.0).as_slice(),
+            &Vector4::new(1.0, 2.0, 3.0, 4.0).as_array()
+        );
+        assert_eq!(Matrix3::identity().as_slice()[4], 1.0);
+        assert_eq!(
+            Matrix2::new(1.0, 2.0, 3.0, 4.0).as_slice(),
+            &[1.0, 2.0, 3.0, 4.0]
+        );
+    }
+
+    #[test]
+    fn test_as_bytes() {
+        let vec = Vector3::new(1.0, 2.0, 3.0);
+        let bytes = vec.as_bytes();
+
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(&bytes[4..8], &2.0f32.to_ne_bytes());
+
+        assert_eq!(Matrix4::identity().as_bytes().len(), 64);
+    }
+}
+
+#[cfg(test)]
+mod generic_tests {
+    use crate::math::*;
+
+    #[test]
+    fn test_integer_vectors() {
+        let a = Vector3i::new(1, -2, 3);
+        let b = Vector3i::new(4, 5, -6);
+
+        assert_eq!(a + b, Vector3i::new(5, 3, -3));
+        assert_eq!(a * 2, Vector3i::new(2, -4, 6));
+        assert_eq!(2 * a, Vector3i::new(2, -4, 6));
+        assert_eq!(-a, Vector3i::new(-1, 2, -3));
+        assert_eq!(a.dot(b), 4 - 10 - 18);
+        assert_eq!(a.cross(b), Vector3i::new(-3, 18, 13));
+        assert_eq!(a.min(b), Vector3i::new(1, -2, -6));
+
+        let mut u = Vector2u::new(4, 9);
+        u /= 2;
+        assert_eq!(u, Vector2u::new(2, 4));
+        assert_eq!(u.x, 2);
+        assert_eq!(u[1], 4);
+    }
+
+    #[test]
+    fn test_f64_vectors() {
+        let mut v = Vector3d::new(3.0, 0.0, 4.0);
+        assert_eq!(v.length(), 5.0);
+
+        v.normalize();
+        assert_eq!(v, Vector3d::new(0.6, 0.0, 0.8));
+    }
+
+    #[test]
+    fn test_named_fields_match_indices() {
+        let mut v = Vector4u::new(1, 2, 3, 4);
+        v.z = 30;
+        assert_eq!(v.as_array(), [1, 2, 30, 4]);
+
+        let mut m = Matrix2i::identity();
+        m.y.x = 5;
+        assert_eq!(m[1], Vector2i::new(5, 1));
+        assert_eq!(m.get_row(0), [1, 5]);
+    }
+
+    #[test]
+    fn test_lossless_convert() {
+        let v = Vector3i::new(1, -2, 3);
+        let d: Vector3d = v.convert();
+        assert_eq!(d, Vector3d::new(1.0, -2.0, 3.0));
+
+        let m = Matrix2::new(0.5, 1.5, 2.5, 3.5);
+        let md: Matrix2d = m.convert();
+        assert_eq!(md, Matrix2d::new(0.5, 1.5, 2.5, 3.5));
+    }
+
+    #[test]
+    fn test_lossy_cast() {
+        let v = Vector3::new(1.9, -2.5, 3.0);
+        assert_eq!(v.cast::<i32>(), Vector3i::new(1, -2, 3));
+
+        // Negative to unsigned saturates like `as`
+        assert_eq!(v.cast::<u32>(), Vector3u::new(1, 0, 3));
+
+        let d = Vector2d::new(0.1, 1.0e40);
+        let f: Vector2 = d.cast();
+        assert_eq!(f, Vector2::new(0.1, f32::INFINITY));
+    }
+
+    #[test]
+    fn test_non_square_multiply_and_transpose() {
+        // 2 rows, 3 columns
+        let a = Matrix::<i32, 2, 3>::from_array([[1, 4], [2, 5], [3, 6]]);
+        // 3 rows, 2 columns
+        let b = a.transpose();
+        assert_eq!(b.as_array(), [[1, 2, 3], [4, 5, 6]]);
+
+        let ab = a * b;
+        assert_eq!(ab, Matrix2i::new(14, 32, 32, 77));
+
+        let ba: Matrix3i = b * a;
+        assert_eq!(ba.get_row(0), [17, 22, 27]);
 
-        assert_eq!(res, 13.93);
+        assert_eq!(a * Vector3i::new(1, 0, -1), Vector2i::new(-2, -2));
+    }
+
+    #[test]
+    fn test_array_conversions() {
+        let v: Vector4 = [1.0, 2.0, 3.0, 4.0].into();
+        let arr: [f32; 4] = v.into();
+        assert_eq!(arr, [1.0, 2.0, 3.0, 4.0]);
     }
 }
 
 #[cfg(test)]
 mod math_tests {
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    use crate::assert_abs_diff_eq;
+    use crate::math::*;
+
     #[test]
-    fn test_radians_to_deg() {}
+    fn test_radians_to_deg() {
+        assert_eq!(convert_to_degrees(PI), 180.0);
+        assert_eq!(convert_to_degrees(FRAC_PI_2), 90.0);
+        assert_eq!(convert_to_degrees(-FRAC_PI_4), -45.0);
+        assert_eq!(convert_to_degrees(0.0), 0.0);
+
+        assert_eq!(Deg::from(Rad(PI)), Deg(180.0));
+        assert_eq!(Deg::from(Rad(FRAC_PI_2)).0, convert_to_degrees(FRAC_PI_2));
+    }
 
     #[test]
-    fn test_deg_to_radians() {}
+    fn test_deg_to_radians() {
+        assert_eq!(convert_to_radians(180.0), PI);
+        assert_eq!(convert_to_radians(90.0), FRAC_PI_2);
+        assert_eq!(convert_to_radians(-45.0), -FRAC_PI_4);
+        assert_abs_diff_eq!(convert_to_radians(convert_to_degrees(1.234)), 1.234, 1e-6);
+
+        assert_eq!(Rad::from(Deg(180.0)), Rad(PI));
+        assert_eq!(Rad::from(Deg(90.0)).0, convert_to_radians(90.0));
+    }
 }
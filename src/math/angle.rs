@@ -0,0 +1,292 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::math::Float;
+
+// Angle in radians. Functions taking impl Into<Rad> accept Rad or Deg, a bare float has to
+// be wrapped so the unit is always spelled out.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct Rad<T = f32>(pub T);
+
+// Angle in degrees, converts to Rad wherever an angle is expected
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct Deg<T = f32>(pub T);
+
+impl<T: Float> Rad<T> {
+    pub fn full_turn() -> Self {
+        Rad(T::PI + T::PI)
+    }
+
+    pub fn asin(ratio: T) -> Self {
+        Rad(ratio.asin())
+    }
+
+    pub fn acos(ratio: T) -> Self {
+        Rad(ratio.acos())
+    }
+
+    pub fn atan2(y: T, x: T) -> Self {
+        Rad(y.atan2(x))
+    }
+
+    pub fn sin(self) -> T {
+        self.0.sin()
+    }
+
+    pub fn cos(self) -> T {
+        self.0.cos()
+    }
+
+    pub fn tan(self) -> T {
+        self.0.tan()
+    }
+
+    pub fn sin_cos(self) -> (T, T) {
+        self.0.sin_cos()
+    }
+}
+
+impl<T: Float> Deg<T> {
+    pub fn full_turn() -> Self {
+        Deg(T::from_f64(360.0))
+    }
+
+    pub fn sin(self) -> T {
+        Rad::from(self).sin()
+    }
+
+    pub fn cos(self) -> T {
+        Rad::from(self).cos()
+    }
+
+    pub fn tan(self) -> T {
+        Rad::from(self).tan()
+    }
+
+    pub fn sin_cos(self) -> (T, T) {
+        Rad::from(self).sin_cos()
+    }
+}
+
+impl<T: Float> From<Deg<T>> for Rad<T> {
+    fn from(degrees: Deg<T>) -> Self {
+        Rad(degrees.0 * (T::PI / T::from_f64(180.0)))
+    }
+}
+
+impl<T: Float> From<Rad<T>> for Deg<T> {
+    fn from(radians: Rad<T>) -> Self {
+        Deg(radians.0 * (T::from_f64(180.0) / T::PI))
+    }
+}
+
+macro_rules! impl_angle {
+    ($($angle:ident),*) => {$(
+        impl<T: Float> $angle<T> {
+            // Wrapped into [0, full turn)
+            pub fn normalized(self) -> Self {
+                let full = Self::full_turn().0;
+                let wrapped = self.0 - (self.0 / full).floor() * full;
+
+                // Tiny negative angles round up to exactly a full turn
+                if wrapped >= full {
+                    return $angle(T::ZERO);
+                }
+
+                $angle(wrapped)
+            }
+
+            // Wrapped into [-half turn, half turn)
+            pub fn normalized_signed(self) -> Self {
+                let half = Self::full_turn() / T::from_f64(2.0);
+                (self + half).normalized() - half
+            }
+        }
+
+        impl<T: Float> Add for $angle<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $angle(self.0 + other.0)
+            }
+        }
+
+        impl<T: Float> Sub for $angle<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $angle(self.0 - other.0)
+            }
+        }
+
+        impl<T: Float> Neg for $angle<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $angle(-self.0)
+            }
+        }
+
+        impl<T: Float> Mul<T> for $angle<T> {
+            type Output = Self;
+
+            fn mul(self, scalar: T) -> Self {
+                $angle(self.0 * scalar)
+            }
+        }
+
+        impl<T: Float> Div<T> for $angle<T> {
+            type Output = Self;
+
+            fn div(self, scalar: T) -> Self {
+                $angle(self.0 / scalar)
+            }
+        }
+
+        // Ratio of two angles
+        impl<T: Float> Div for $angle<T> {
+            type Output = T;
+
+            fn div(self, other: Self) -> T {
+                self.0 / other.0
+            }
+        }
+
+        impl<T: Float> AddAssign for $angle<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Float> SubAssign for $angle<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Float> MulAssign<T> for $angle<T> {
+            fn mul_assign(&mut self, scalar: T) {
+                *self = *self * scalar;
+            }
+        }
+
+        impl<T: Float> DivAssign<T> for $angle<T> {
+            fn div_assign(&mut self, scalar: T) {
+                *self = *self / scalar;
+            }
+        }
+
+        impl Mul<$angle<f32>> for f32 {
+            type Output = $angle<f32>;
+
+            fn mul(self, angle: $angle<f32>) -> $angle<f32> {
+                angle * self
+            }
+        }
+
+        impl Mul<$angle<f64>> for f64 {
+            type Output = $angle<f64>;
+
+            fn mul(self, angle: $angle<f64>) -> $angle<f64> {
+                angle * self
+            }
+        }
+    )*};
+}
+
+impl_angle! { Rad, Deg }
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod angle_tests {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    use crate::assert_abs_diff_eq;
+    use crate::math::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Rad::from(Deg(180.0)), Rad(PI));
+        assert_eq!(Deg::from(Rad(FRAC_PI_2)), Deg(90.0));
+
+        let back: Deg<f64> = Rad::from(Deg(37.5f64)).into();
+        assert_abs_diff_eq!(back.0, 37.5, 1e-12);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(Deg(30.0) + Deg(60.0), Deg(90.0));
+        assert_eq!(Deg(30.0) - Deg(60.0), Deg(-30.0));
+        assert_eq!(-Rad(1.0), Rad(-1.0));
+        assert_eq!(Deg(45.0) * 2.0, Deg(90.0));
+        assert_eq!(2.0 * Deg(45.0), Deg(90.0));
+        assert_eq!(Rad(3.0) / 2.0, Rad(1.5));
+        assert_eq!(Deg(90.0) / Deg(45.0), 2.0);
+
+        let mut angle = Deg(10.0);
+        angle += Deg(5.0);
+        angle -= Deg(3.0);
+        angle *= 3.0;
+        angle /= 4.0;
+        assert_eq!(angle, Deg(9.0));
+
+        assert!(Deg(10.0) < Deg(20.0));
+    }
+
+    #[test]
+    fn test_normalization() {
+        assert_eq!(Deg(370.0).normalized(), Deg(10.0));
+        assert_eq!(Deg(-90.0).normalized(), Deg(270.0));
+        assert_eq!(Deg(360.0).normalized(), Deg(0.0));
+        assert_eq!(Deg(-0.5).normalized(), Deg(359.5));
+        assert_eq!(Deg(-1e-6f32).normalized(), Deg(0.0));
+
+        assert_eq!(Deg(270.0).normalized_signed(), Deg(-90.0));
+        assert_eq!(Deg(180.0).normalized_signed(), Deg(-180.0));
+        assert_eq!(Deg(-45.0).normalized_signed(), Deg(-45.0));
+
+        let rad = Rad(5.0 * PI).normalized();
+        assert_abs_diff_eq!(rad.0, PI, 1e-5);
+        assert!(rad.0 >= 0.0 && rad < Rad::full_turn());
+    }
+
+    #[test]
+    fn test_trig() {
+        assert_abs_diff_eq!(Deg(30.0).sin(), 0.5, 1e-6);
+        assert_abs_diff_eq!(Deg(60.0).cos(), 0.5, 1e-6);
+        assert_abs_diff_eq!(Deg(45.0).tan(), 1.0, 1e-6);
+        assert_eq!(Rad(0.3).sin_cos(), 0.3f32.sin_cos());
+
+        assert_abs_diff_eq!(Deg::from(Rad::asin(0.5)).0, 30.0, 1e-4);
+        assert_abs_diff_eq!(Deg::from(Rad::acos(0.5)).0, 60.0, 1e-4);
+        assert_abs_diff_eq!(Deg::from(Rad::atan2(1.0, -1.0)).0, 135.0, 1e-4);
+    }
+
+    #[test]
+    fn test_apis_accept_degrees() {
+        assert_eq!(
+            Matrix4::perspective(Deg(90.0), 1.0, 0.1, 10.0),
+            Matrix4::perspective(Rad(FRAC_PI_2), 1.0, 0.1, 10.0)
+        );
+
+        let axis = Vector3::new(0.0, 1.0, 0.0);
+        assert_eq!(
+            Quaternion::from_axis_angle(axis, Deg(90.0)),
+            Quaternion::from_axis_angle(axis, Rad(FRAC_PI_2))
+        );
+
+        let mut v = Vector4::new(1.0, 0.0, 0.0, 1.0);
+        v.rotate(Axis::Z, Deg(90.0));
+        assert_abs_diff_eq!(v, Vector4::new(0.0, 1.0, 0.0, 1.0), 1e-6);
+
+        let q = Quaternion::from_euler(Deg(90.0), Rad(0.0), Rad(0.0), EulerOrder::XYZ);
+        assert_abs_diff_eq!(
+            q.rotate_vector(Vector3::new(0.0, 1.0, 0.0)),
+            Vector3::new(0.0, 0.0, 1.0),
+            1e-6
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use crate::math::{Matrix, Quaternion, Vector};
+
+// Floating point comparisons that allow for rounding error.
+// Vectors, matrices and quaternions compare component-wise and are equal when every
+// component is. Note that a quaternion q and -q are the same rotation but not approx equal.
+pub trait ApproxEq {
+    type Epsilon: Copy;
+
+    // Used by approx_eq and assert_approx_eq!, loose enough for a few chained rotations
+    const DEFAULT_EPSILON: Self::Epsilon;
+    const DEFAULT_MAX_RELATIVE: Self::Epsilon;
+    const DEFAULT_MAX_ULPS: u32 = 4;
+
+    // |a - b| <= epsilon, good near zero but meaningless for large values
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool;
+
+    // |a - b| <= max_relative * max(|a|, |b|), with epsilon as an absolute floor near zero
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool;
+
+    // At most max_ulps representable floats apart, with epsilon as an absolute floor near zero
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool;
+
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.relative_eq(other, Self::DEFAULT_EPSILON, Self::DEFAULT_MAX_RELATIVE)
+    }
+}
+
+macro_rules! impl_approx_eq_float {
+    ($($t:ident: $bits:ident, $epsilon:literal, $max_relative:literal);+) => {
+        $(
+            impl ApproxEq for $t {
+                type Epsilon = $t;
+
+                const DEFAULT_EPSILON: $t = $epsilon;
+                const DEFAULT_MAX_RELATIVE: $t = $max_relative;
+
+                fn abs_diff_eq(&self, other: &$t, epsilon: $t) -> bool {
+                    // Equal infinities have a NaN difference
+                    self == other || (self - other).abs() <= epsilon
+                }
+
+                fn relative_eq(&self, other: &$t, epsilon: $t, max_relative: $t) -> bool {
+                    if self.abs_diff_eq(other, epsilon) {
+                        return true;
+                    }
+
+                    if self.is_infinite() || other.is_infinite() {
+                        return false;
+                    }
+
+                    let largest = self.abs().max(other.abs());
+                    (self - other).abs() <= largest * max_relative
+                }
+
+                fn ulps_eq(&self, other: &$t, epsilon: $t, max_ulps: u32) -> bool {
+                    if self.abs_diff_eq(other, epsilon) {
+                        return true;
+                    }
+
+                    if self.is_nan() || other.is_nan() {
+                        return false;
+                    }
+
+                    if self.is_sign_negative() != other.is_sign_negative() {
+                        return false;
+                    }
+
+                    // Same sign floats are ordered like their bit patterns
+                    let distance = self.to_bits() as $bits - other.to_bits() as $bits;
+                    distance.unsigned_abs() <= max_ulps as _
+                }
+            }
+        )+
+    };
+}
+
+impl_approx_eq_float! {
+    f32: i32, 1.0e-6, 1.0e-5;
+    f64: i64, 1.0e-12, 1.0e-10
+}
+
+impl<T: ApproxEq, const N: usize> ApproxEq for Vector<T, N> {
+    type Epsilon = T::Epsilon;
+
+    const DEFAULT_EPSILON: T::Epsilon = T::DEFAULT_EPSILON;
+    const DEFAULT_MAX_RELATIVE: T::Epsilon = T::DEFAULT_MAX_RELATIVE;
+    const DEFAULT_MAX_ULPS: u32 = T::DEFAULT_MAX_ULPS;
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: T::Epsilon) -> bool {
+        (0..N).all(|i| self[i].abs_diff_eq(&other[i], epsilon))
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+        (0..N).all(|i| self[i].relative_eq(&other[i], epsilon, max_relative))
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+        (0..N).all(|i| self[i].ulps_eq(&other[i], epsilon, max_ulps))
+    }
+}
+
+impl<T: ApproxEq, const R: usize, const C: usize> ApproxEq for Matrix<T, R, C> {
+    type Epsilon = T::Epsilon;
+
+    const DEFAULT_EPSILON: T::Epsilon = T::DEFAULT_EPSILON;
+    const DEFAULT_MAX_RELATIVE: T::Epsilon = T::DEFAULT_MAX_RELATIVE;
+    const DEFAULT_MAX_ULPS: u32 = T::DEFAULT_MAX_ULPS;
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: T::Epsilon) -> bool {
+        (0..C).all(|i| self[i].abs_diff_eq(&other[i], epsilon))
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+        (0..C).all(|i| self[i].relative_eq(&other[i], epsilon, max_relative))
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+        (0..C).all(|i| self[i].ulps_eq(&other[i], epsilon, max_ulps))
+    }
+}
+
+impl ApproxEq for Quaternion {
+    type Epsilon = f32;
+
+    const DEFAULT_EPSILON: f32 = f32::DEFAULT_EPSILON;
+    const DEFAULT_MAX_RELATIVE: f32 = f32::DEFAULT_MAX_RELATIVE;
+
+    fn abs_diff_eq(&self, other: &Quaternion, epsilon: f32) -> bool {
+        components(self).abs_diff_eq(&components(other), epsilon)
+    }
+
+    fn relative_eq(&self, other: &Quaternion, epsilon: f32, max_relative: f32) -> bool {
+        components(self).relative_eq(&components(other), epsilon, max_relative)
+    }
+
+    fn ulps_eq(&self, other: &Quaternion, epsilon: f32, max_ulps: u32) -> bool {
+        components(self).ulps_eq(&components(other), epsilon, max_ulps)
+    }
+}
+
+fn components(q: &Quaternion) -> Vector<f32, 4> {
+    Vector::<f32, 4>::new(q.x, q.y, q.z, q.w)
+}
+
+// assert_eq! for ApproxEq types, using approx_eq and its default tolerances
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let (left, right) = (&$left, &$right);
+        if !$crate::math::ApproxEq::approx_eq(left, right) {
+            panic!(
+                "assertion `left approx_eq right` failed\n  left: {:?}\n right: {:?}",
+                left, right
+            );
+        }
+    }};
+}
+
+// assert_approx_eq! with an absolute tolerance
+#[macro_export]
+macro_rules! assert_abs_diff_eq {
+    ($left:expr, $right:expr, $epsilon:expr $(,)?) => {{
+        let (left, right) = (&$left, &$right);
+        if !$crate::math::ApproxEq::abs_diff_eq(left, right, $epsilon) {
+            panic!(
+                "assertion `left abs_diff_eq right` failed (epsilon: {:?})\n  left: {:?}\n right: {:?}",
+                $epsilon, left, right
+            );
+        }
+    }};
+}
+
+// assert_approx_eq! with a relative tolerance
+#[macro_export]
+macro_rules! assert_relative_eq {
+    ($left:expr, $right:expr, $epsilon:expr, $max_relative:expr $(,)?) => {{
+        let (left, right) = (&$left, &$right);
+        if !$crate::math::ApproxEq::relative_eq(left, right, $epsilon, $max_relative) {
+            panic!(
+                "assertion `left relative_eq right` failed (epsilon: {:?}, max_relative: {:?})\n  left: {:?}\n right: {:?}",
+                $epsilon, $max_relative, left, right
+            );
+        }
+    }};
+}
+
+// assert_approx_eq! with a tolerance in ULPs
+#[macro_export]
+macro_rules! assert_ulps_eq {
+    ($left:expr, $right:expr, $epsilon:expr, $max_ulps:expr $(,)?) => {{
+        let (left, right) = (&$left, &$right);
+        if !$crate::math::ApproxEq::ulps_eq(left, right, $epsilon, $max_ulps) {
+            panic!(
+                "assertion `left ulps_eq right` failed (epsilon: {:?}, max_ulps: {:?})\n  left: {:?}\n right: {:?}",
+                $epsilon, $max_ulps, left, right
+            );
+        }
+    }};
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod approx_tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::math::*;
+
+    #[test]
+    fn test_f32_abs_diff() {
+        assert!(1.0f32.abs_diff_eq(&1.05, 0.1));
+        assert!(!1.0f32.abs_diff_eq(&1.2, 0.1));
+        assert!(f32::INFINITY.abs_diff_eq(&f32::INFINITY, 0.0));
+        assert!(!f32::NAN.abs_diff_eq(&f32::NAN, 1.0));
+    }
+
+    #[test]
+    fn test_f32_relative() {
+        // Absolute epsilon would be far too strict here
+        assert!(1.0e6f32.relative_eq(&1.000001e6, 0.0, 1.0e-5));
+        assert!(!1.0e6f32.relative_eq(&1.1e6, 0.0, 1.0e-5));
+
+        // Relative is useless near zero, epsilon covers it
+        assert!(!1.0e-9f32.relative_eq(&-1.0e-9, 0.0, 1.0e-5));
+        assert!(1.0e-9f32.relative_eq(&-1.0e-9, 1.0e-6, 1.0e-5));
+
+        assert!(!f32::MAX.relative_eq(&f32::INFINITY, 0.0, 1.0));
+        assert!(!f32::NAN.relative_eq(&1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_f32_ulps() {
+        let one_up = f32::from_bits(1.0f32.to_bits() + 1);
+        let four_up = f32::from_bits(1.0f32.to_bits() + 4);
+
+        assert!(1.0f32.ulps_eq(&one_up, 0.0, 1));
+        assert!(!1.0f32.ulps_eq(&four_up, 0.0, 3));
+        assert!(1.0f32.ulps_eq(&four_up, 0.0, 4));
+
+        // Opposite signs are never close in ULPs, but zeros are equal
+        assert!(!1.0e-40f32.ulps_eq(&-1.0e-40, 0.0, 1000));
+        assert!(0.0f32.ulps_eq(&-0.0, 0.0, 0));
+
+        assert!(!f32::NAN.ulps_eq(&f32::NAN, 0.0, u32::MAX));
+    }
+
+    #[test]
+    fn test_f64() {
+        assert!((0.1f64 + 0.2).approx_eq(&0.3));
+        assert!((0.1f64 + 0.2).ulps_eq(&0.3, 0.0, 1));
+    }
+
+    #[test]
+    fn test_vectors_and_matrices() {
+        let mut v = Vector4::new(1.0, 0.0, 0.0, 1.0);
+        v.rotate(Axis::Z, Rad(FRAC_PI_2));
+
+        // cos(pi / 2) isn't exactly 0
+        assert_ne!(v, Vector4::new(0.0, 1.0, 0.0, 1.0));
+        assert_approx_eq!(v, Vector4::new(0.0, 1.0, 0.0, 1.0));
+
+        assert!(!Vector3::new(1.0, 2.0, 3.0).approx_eq(&Vector3::new(1.0, 2.0, 3.1)));
+
+        let m = Matrix4::perspective(Rad(1.0), 1.5, 0.1, 100.0);
+        let round_trip = m.inverse().unwrap().inverse().unwrap();
+        assert_approx_eq!(round_trip, m);
+        assert_abs_diff_eq!(round_trip, m, 1.0e-4);
+
+        let d = Vector2d::new(1.0, 2.0);
+        assert_relative_eq!(d * 3.0, Vector2d::new(3.0, 6.0), 0.0, 1.0e-15);
+    }
+
+    #[test]
+    fn test_quaternions() {
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), Rad(1.0));
+        let round_trip = Quaternion::from_matrix3(q.to_matrix3());
+
+        assert_approx_eq!(round_trip, q);
+        assert_ulps_eq!(q, q, 0.0, 0);
+
+        // Same rotation, different quaternion
+        assert!(!q.approx_eq(&-q));
+    }
+
+    #[test]
+    #[should_panic(expected = "left approx_eq right")]
+    fn test_assert_approx_eq_panics() {
+        assert_approx_eq!(Vector2::new(1.0, 2.0), Vector2::new(1.0, 2.5));
+    }
+}
@@ -0,0 +1,679 @@
+use crate::math::Vector;
+
+// A parametric curve over Vector2 or Vector3, t goes from 0 at the start to 1 at the end.
+// Splines through several segments map the whole spline to [0, 1], t is clamped.
+pub trait Curve<const N: usize> {
+    fn position(&self, t: f32) -> Vector<f32, N>;
+
+    // First derivative with respect to t, its length is the speed along the curve
+    fn derivative(&self, t: f32) -> Vector<f32, N>;
+
+    // Unit direction of travel, zero where the curve stops moving
+    fn tangent(&self, t: f32) -> Vector<f32, N> {
+        let derivative = self.derivative(t);
+        let length = derivative.length_squared().sqrt();
+
+        if length == 0.0 {
+            return derivative;
+        }
+
+        derivative / length
+    }
+
+    // Length approximated by that many straight segments
+    fn arc_length(&self, samples: usize) -> f32
+    where
+        Self: Sized,
+    {
+        ArcLengthTable::new(self, samples).length()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuadraticBezier<const N: usize> {
+    pub p0: Vector<f32, N>,
+    pub p1: Vector<f32, N>,
+    pub p2: Vector<f32, N>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubicBezier<const N: usize> {
+    pub p0: Vector<f32, N>,
+    pub p1: Vector<f32, N>,
+    pub p2: Vector<f32, N>,
+    pub p3: Vector<f32, N>,
+}
+
+// One segment from p0 to p1 with the derivatives m0 and m1 at the ends
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hermite<const N: usize> {
+    pub p0: Vector<f32, N>,
+    pub m0: Vector<f32, N>,
+    pub p1: Vector<f32, N>,
+    pub m1: Vector<f32, N>,
+}
+
+// Uniform Catmull-Rom spline, passes through every point.
+// Open splines mirror the end points to get the first and last tangents.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CatmullRom<const N: usize> {
+    points: Vec<Vector<f32, N>>,
+    closed: bool,
+}
+
+// Uniform cubic B-spline, C2 continuous but only approximates its control points.
+// Starts near control_points[1] and ends near the second to last point.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BSpline<const N: usize> {
+    control_points: Vec<Vector<f32, N>>,
+}
+
+// Cumulative length at evenly spaced t, maps distances along a curve back to t
+// for constant speed movement.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ArcLengthTable {
+    lengths: Vec<f32>,
+}
+
+impl<const N: usize> QuadraticBezier<N> {
+    pub fn new(p0: Vector<f32, N>, p1: Vector<f32, N>, p2: Vector<f32, N>) -> Self {
+        Self { p0, p1, p2 }
+    }
+}
+
+impl<const N: usize> Curve<N> for QuadraticBezier<N> {
+    fn position(&self, t: f32) -> Vector<f32, N> {
+        let u = 1.0 - t;
+        self.p0 * (u * u) + self.p1 * (2.0 * u * t) + self.p2 * (t * t)
+    }
+
+    fn derivative(&self, t: f32) -> Vector<f32, N> {
+        (self.p1 - self.p0) * (2.0 * (1.0 - t)) + (self.p2 - self.p1) * (2.0 * t)
+    }
+}
+
+impl<const N: usize> CubicBezier<N> {
+    pub fn new(
+        p0: Vector<f32, N>,
+        p1: Vector<f32, N>,
+        p2: Vector<f32, N>,
+        p3: Vector<f32, N>,
+    ) -> Self {
+        Self { p0, p1, p2, p3 }
+    }
+
+    // Splits at t into two curves that together trace the same path (de Casteljau)
+    pub fn split(&self, t: f32) -> (Self, Self) {
+        let p01 = lerp(self.p0, self.p1, t);
+        let p12 = lerp(self.p1, self.p2, t);
+        let p23 = lerp(self.p2, self.p3, t);
+        let p012 = lerp(p01, p12, t);
+        let p123 = lerp(p12, p23, t);
+        let middle = lerp(p012, p123, t);
+
+        (
+            Self::new(self.p0, p01, p012, middle),
+            Self::new(middle, p123, p23, self.p3),
+        )
+    }
+}
+
+impl<const N: usize> Curve<N> for CubicBezier<N> {
+    fn position(&self, t: f32) -> Vector<f32, N> {
+        let u = 1.0 - t;
+        self.p0 * (u * u * u)
+            + self.p1 * (3.0 * u * u * t)
+            + self.p2 * (3.0 * u * t * t)
+            + self.p3 * (t * t * t)
+    }
+
+    fn derivative(&self, t: f32) -> Vector<f32, N> {
+        let u = 1.0 - t;
+        (self.p1 - self.p0) * (3.0 * u * u)
+            + (self.p2 - self.p1) * (6.0 * u * t)
+            + (self.p3 - self.p2) * (3.0 * t * t)
+    }
+}
+
+impl<const N: usize> From<Hermite<N>> for CubicBezier<N> {
+    fn from(hermite: Hermite<N>) -> Self {
+        CubicBezier::new(
+            hermite.p0,
+            hermite.p0 + hermite.m0 / 3.0,
+            hermite.p1 - hermite.m1 / 3.0,
+            hermite.p1,
+        )
+    }
+}
+
+impl<const N: usize> Hermite<N> {
+    pub fn new(
+        p0: Vector<f32, N>,
+        m0: Vector<f32, N>,
+        p1: Vector<f32, N>,
+        m1: Vector<f32, N>,
+    ) -> Self {
+        Self { p0, m0, p1, m1 }
+    }
+}
+
+impl<const N: usize> Curve<N> for Hermite<N> {
+    fn position(&self, t: f32) -> Vector<f32, N> {
+        let t2 = t * t;
+        let t3 = t2 * t;
+        self.p0 * (2.0 * t3 - 3.0 * t2 + 1.0)
+            + self.m0 * (t3 - 2.0 * t2 + t)
+            + self.p1 * (-2.0 * t3 + 3.0 * t2)
+            + self.m1 * (t3 - t2)
+    }
+
+    fn derivative(&self, t: f32) -> Vector<f32, N> {
+        let t2 = t * t;
+        self.p0 * (6.0 * t2 - 6.0 * t)
+            + self.m0 * (3.0 * t2 - 4.0 * t + 1.0)
+            + self.p1 * (-6.0 * t2 + 6.0 * t)
+            + self.m1 * (3.0 * t2 - 2.0 * t)
+    }
+}
+
+impl<const N: usize> CatmullRom<N> {
+    // Returns None for fewer than 2 points
+    pub fn new(points: Vec<Vector<f32, N>>) -> Option<Self> {
+        if points.len() < 2 {
+            return None;
+        }
+
+        Some(Self {
+            points,
+            closed: false,
+        })
+    }
+
+    // Loops back from the last point to the first. Returns None for fewer than 3 points.
+    pub fn new_closed(points: Vec<Vector<f32, N>>) -> Option<Self> {
+        if points.len() < 3 {
+            return None;
+        }
+
+        Some(Self {
+            points,
+            closed: true,
+        })
+    }
+
+    pub fn points(&self) -> &[Vector<f32, N>] {
+        &self.points
+    }
+
+    pub fn segment_count(&self) -> usize {
+        if self.closed {
+            self.points.len()
+        } else {
+            self.points.len() - 1
+        }
+    }
+
+    // Point i, wrapping around or mirroring the ends for indices outside the list
+    fn point(&self, i: isize) -> Vector<f32, N> {
+        let len = self.points.len() as isize;
+
+        if self.closed {
+            return self.points[i.rem_euclid(len) as usize];
+        }
+
+        if i < 0 {
+            self.points[0] * 2.0 - self.points[1]
+        } else if i >= len {
+            self.points[len as usize - 1] * 2.0 - self.points[len as usize - 2]
+        } else {
+            self.points[i as usize]
+        }
+    }
+
+    // Segment i as a Hermite curve with tangents (p[i + 1] - p[i - 1]) / 2
+    pub fn segment(&self, i: usize) -> Hermite<N> {
+        if i >= self.segment_count() {
+            panic!("Index provided to CatmullRom::segment is out of bounds!");
+        }
+
+        let i = i as isize;
+        let p0 = self.point(i);
+        let p1 = self.point(i + 1);
+
+        Hermite::new(
+            p0,
+            (p1 - self.point(i - 1)) * 0.5,
+            p1,
+            (self.point(i + 2) - p0) * 0.5,
+        )
+    }
+}
+
+impl<const N: usize> Curve<N> for CatmullRom<N> {
+    fn position(&self, t: f32) -> Vector<f32, N> {
+        let (i, local) = split_t(t, self.segment_count());
+        self.segment(i).position(local)
+    }
+
+    fn derivative(&self, t: f32) -> Vector<f32, N> {
+        let count = self.segment_count();
+        let (i, local) = split_t(t, count);
+        self.segment(i).derivative(local) * count as f32
+    }
+}
+
+impl<const N: usize> BSpline<N> {
+    // Returns None for fewer than 4 control points
+    pub fn new(control_points: Vec<Vector<f32, N>>) -> Option<Self> {
+        if control_points.len() < 4 {
+            return None;
+        }
+
+        Some(Self { control_points })
+    }
+
+    pub fn control_points(&self) -> &[Vector<f32, N>] {
+        &self.control_points
+    }
+
+    pub fn segment_count(&self) -> usize {
+        self.control_points.len() - 3
+    }
+
+    fn segment_points(&self, i: usize) -> [Vector<f32, N>; 4] {
+        let p = &self.control_points;
+        [p[i], p[i + 1], p[i + 2], p[i + 3]]
+    }
+}
+
+impl<const N: usize> Curve<N> for BSpline<N> {
+    fn position(&self, t: f32) -> Vector<f32, N> {
+        let (i, t) = split_t(t, self.segment_count());
+        let [p0, p1, p2, p3] = self.segment_points(i);
+        let u = 1.0 - t;
+        let t2 = t * t;
+        let t3 = t2 * t;
+
+        (p0 * (u * u * u)
+            + p1 * (3.0 * t3 - 6.0 * t2 + 4.0)
+            + p2 * (-3.0 * t3 + 3.0 * t2 + 3.0 * t + 1.0)
+            + p3 * t3)
+            / 6.0
+    }
+
+    fn derivative(&self, t: f32) -> Vector<f32, N> {
+        let count = self.segment_count();
+        let (i, t) = split_t(t, count);
+        let [p0, p1, p2, p3] = self.segment_points(i);
+        let u = 1.0 - t;
+        let t2 = t * t;
+
+        (p0 * (-3.0 * u * u)
+            + p1 * (9.0 * t2 - 12.0 * t)
+            + p2 * (-9.0 * t2 + 6.0 * t + 3.0)
+            + p3 * (3.0 * t2))
+            * (count as f32 / 6.0)
+    }
+}
+
+// Global t to (segment index, t within the segment)
+fn split_t(t: f32, segments: usize) -> (usize, f32) {
+    let scaled = t.clamp(0.0, 1.0) * segments as f32;
+    let i = (scaled.floor() as usize).min(segments - 1);
+
+    (i, scaled - i as f32)
+}
+
+impl ArcLengthTable {
+    // @params: samples is the number of straight segments, more is more accurate. At least 1.
+    pub fn new<const N: usize>(curve: &impl Curve<N>, samples: usize) -> Self {
+        let samples = samples.max(1);
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut previous = curve.position(0.0);
+        let mut total = 0.0;
+
+        lengths.push(0.0);
+
+        for i in 1..=samples {
+            let point = curve.position(i as f32 / samples as f32);
+            total += (point - previous).length_squared().sqrt();
+            lengths.push(total);
+            previous = point;
+        }
+
+        Self { lengths }
+    }
+
+    // A table saved from lengths(). None unless there are at least 2 lengths, starting at 0
+    // and never decreasing.
+    pub fn from_lengths(lengths: Vec<f32>) -> Option<Self> {
+        if lengths.len() < 2 || lengths[0] != 0.0 || !lengths.is_sorted() {
+            return None;
+        }
+
+        Some(Self { lengths })
+    }
+
+    // Cumulative length at each sample, the first is always 0
+    pub fn lengths(&self) -> &[f32] {
+        &self.lengths
+    }
+
+    pub fn length(&self) -> f32 {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    // t at that distance from the start, clamped to the curve
+    pub fn t_at_distance(&self, distance: f32) -> f32 {
+        let samples = self.lengths.len() - 1;
+
+        if distance <= 0.0 {
+            return 0.0;
+        }
+        if distance >= self.length() {
+            return 1.0;
+        }
+
+        // First sample at or past the distance, it's never 0 since distance > 0
+        let i = self.lengths.partition_point(|length| *length < distance);
+        let start = self.lengths[i - 1];
+        let span = self.lengths[i] - start;
+        let fraction = if span == 0.0 {
+            0.0
+        } else {
+            (distance - start) / span
+        };
+
+        (i as f32 - 1.0 + fraction) / samples as f32
+    }
+
+    // t at that fraction of the total length, 0.5 is halfway along the curve
+    pub fn t_at_fraction(&self, fraction: f32) -> f32 {
+        self.t_at_distance(fraction * self.length())
+    }
+
+    // count points spaced evenly along the curve, including both ends
+    pub fn sample_evenly<const N: usize>(
+        &self,
+        curve: &impl Curve<N>,
+        count: usize,
+    ) -> Vec<Vector<f32, N>> {
+        match count {
+            0 => Vec::new(),
+            1 => vec![curve.position(0.0)],
+            _ => (0..count)
+                .map(|i| curve.position(self.t_at_fraction(i as f32 / (count - 1) as f32)))
+                .collect(),
+        }
+    }
+}
+
+fn lerp<const N: usize>(a: Vector<f32, N>, b: Vector<f32, N>, t: f32) -> Vector<f32, N> {
+    a + (b - a) * t
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod curve_tests {
+    use crate::assert_abs_diff_eq;
+    use crate::math::*;
+
+    fn sample_cubic() -> CubicBezier<3> {
+        CubicBezier::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 2.0, 0.0),
+            Vector3::new(3.0, 2.0, 1.0),
+            Vector3::new(4.0, 0.0, 1.0),
+        )
+    }
+
+    fn sample_points() -> Vec<Vector3> {
+        vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 1.0, 0.0),
+            Vector3::new(3.0, 1.0, -1.0),
+            Vector3::new(4.0, 0.0, 2.0),
+            Vector3::new(6.0, -2.0, 2.0),
+        ]
+    }
+
+    // Central difference against the analytic derivative
+    fn assert_derivative_matches(curve: &impl Curve<3>) {
+        let h = 1e-3;
+
+        for i in 1..20 {
+            let t = i as f32 / 20.0;
+            let numeric = (curve.position(t + h) - curve.position(t - h)) / (2.0 * h);
+            let analytic = curve.derivative(t);
+
+            assert!(
+                (numeric - analytic).length() < 2e-2 * analytic.length().max(1.0),
+                "t = {}: {:?} != {:?}",
+                t,
+                numeric,
+                analytic
+            );
+        }
+    }
+
+    #[test]
+    fn test_bezier_endpoints() {
+        let quadratic = QuadraticBezier::new(
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 2.0),
+            Vector2::new(2.0, 0.0),
+        );
+        assert_eq!(quadratic.position(0.0), Vector2::new(0.0, 0.0));
+        assert_eq!(quadratic.position(0.5), Vector2::new(1.0, 1.0));
+        assert_eq!(quadratic.position(1.0), Vector2::new(2.0, 0.0));
+        // Tangent at the ends points at the control point
+        assert_eq!(quadratic.derivative(0.0), Vector2::new(2.0, 4.0));
+
+        let cubic = sample_cubic();
+        assert_eq!(cubic.position(0.0), cubic.p0);
+        assert_eq!(cubic.position(1.0), cubic.p3);
+        assert_eq!(cubic.derivative(0.0), (cubic.p1 - cubic.p0) * 3.0);
+        assert_eq!(cubic.derivative(1.0), (cubic.p3 - cubic.p2) * 3.0);
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let quadratic = QuadraticBezier::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(2.0, 0.0, -1.0),
+        );
+        assert_derivative_matches(&quadratic);
+        assert_derivative_matches(&sample_cubic());
+        assert_derivative_matches(&Hermite::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(3.0, 0.0, 1.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            Vector3::new(0.0, -2.0, 0.0),
+        ));
+        assert_derivative_matches(&CatmullRom::new(sample_points()).unwrap());
+        assert_derivative_matches(&BSpline::new(sample_points()).unwrap());
+    }
+
+    #[test]
+    fn test_tangent() {
+        let cubic = sample_cubic();
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            let tangent = cubic.tangent(t);
+            assert!((tangent.length() - 1.0).abs() < 1e-5);
+            assert!(tangent.dot(cubic.derivative(t)) > 0.0);
+        }
+
+        // A curve that doesn't move has no direction
+        let point = QuadraticBezier::new(
+            Vector2::splat(1.0),
+            Vector2::splat(1.0),
+            Vector2::splat(1.0),
+        );
+        assert_eq!(point.tangent(0.5), Vector2::zero());
+    }
+
+    #[test]
+    fn test_cubic_split() {
+        let cubic = sample_cubic();
+        let (first, second) = cubic.split(0.3);
+
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            assert_abs_diff_eq!(first.position(t), cubic.position(t * 0.3), 1e-5);
+            assert_abs_diff_eq!(second.position(t), cubic.position(0.3 + t * 0.7), 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_hermite_matches_bezier() {
+        let hermite = Hermite::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(3.0, 0.0, 1.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            Vector3::new(0.0, -2.0, 0.0),
+        );
+        let bezier = CubicBezier::from(hermite);
+
+        assert_eq!(hermite.position(0.0), hermite.p0);
+        assert_eq!(hermite.position(1.0), hermite.p1);
+        assert_eq!(hermite.derivative(0.0), hermite.m0);
+        assert_eq!(hermite.derivative(1.0), hermite.m1);
+
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            assert_abs_diff_eq!(hermite.position(t), bezier.position(t), 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_catmull_rom_passes_through_points() {
+        let points = sample_points();
+        let spline = CatmullRom::new(points.clone()).unwrap();
+
+        assert_eq!(spline.segment_count(), 4);
+        for (i, point) in points.iter().enumerate() {
+            assert_abs_diff_eq!(spline.position(i as f32 / 4.0), *point, 1e-5);
+        }
+
+        // Tangent at an inner point is half the difference of its neighbours, scaled to global t
+        assert_abs_diff_eq!(
+            spline.derivative(0.25),
+            (points[2] - points[0]) * 0.5 * 4.0,
+            1e-4
+        );
+
+        // Closed splines come back to the start
+        let closed = CatmullRom::new_closed(points.clone()).unwrap();
+        assert_eq!(closed.segment_count(), 5);
+        assert_abs_diff_eq!(closed.position(1.0), points[0], 1e-5);
+        assert_abs_diff_eq!(closed.derivative(0.0), closed.derivative(1.0), 1e-4);
+
+        assert_eq!(CatmullRom::<3>::new(vec![Vector3::zero()]), None);
+        assert_eq!(CatmullRom::<3>::new_closed(points[..2].to_vec()), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_catmull_rom_segment_out_of_bounds() {
+        CatmullRom::new(sample_points()).unwrap().segment(4);
+    }
+
+    #[test]
+    fn test_bspline() {
+        // Evenly spaced points on a line give a straight line at constant speed
+        let line = BSpline::new((0..6).map(|i| Vector2::new(i as f32, 0.0)).collect()).unwrap();
+        assert_eq!(line.segment_count(), 3);
+        assert!((line.position(0.0) - Vector2::new(1.0, 0.0)).length() < 1e-6);
+        assert!((line.position(0.5) - Vector2::new(2.5, 0.0)).length() < 1e-6);
+        assert!((line.position(1.0) - Vector2::new(4.0, 0.0)).length() < 1e-6);
+        assert!((line.derivative(0.3) - Vector2::new(3.0, 0.0)).length() < 1e-5);
+
+        // Continuous position and derivative across segment joins
+        let spline = BSpline::new(sample_points()).unwrap();
+        let join = 0.5;
+        assert_abs_diff_eq!(
+            spline.position(join - 1e-4),
+            spline.position(join + 1e-4),
+            1e-2
+        );
+        assert_abs_diff_eq!(
+            spline.derivative(join - 1e-4),
+            spline.derivative(join + 1e-4),
+            1e-2
+        );
+
+        assert_eq!(BSpline::<3>::new(sample_points()[..3].to_vec()), None);
+    }
+
+    #[test]
+    fn test_arc_length() {
+        // Collinear control points, unevenly spaced so t isn't proportional to distance
+        let line = CubicBezier::new(
+            Vector3::zero(),
+            Vector3::new(0.5, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(10.0, 0.0, 0.0),
+        );
+        assert!((line.arc_length(64) - 10.0).abs() < 1e-4);
+
+        // Standard quarter circle approximation of radius 1
+        let k = 0.552_284_8;
+        let arc = CubicBezier::new(
+            Vector2::new(1.0, 0.0),
+            Vector2::new(1.0, k),
+            Vector2::new(k, 1.0),
+            Vector2::new(0.0, 1.0),
+        );
+        assert!((arc.arc_length(256) - std::f32::consts::FRAC_PI_2).abs() < 1e-3);
+
+        let table = ArcLengthTable::new(&line, 256);
+        assert_eq!(table.t_at_distance(-1.0), 0.0);
+        assert_eq!(table.t_at_distance(20.0), 1.0);
+        for distance in [1.0, 2.5, 5.0, 9.0] {
+            let t = table.t_at_distance(distance);
+            assert!((line.position(t).x - distance).abs() < 1e-2, "{}", distance);
+        }
+
+        let copy = ArcLengthTable::from_lengths(table.lengths().to_vec()).unwrap();
+        assert_eq!(copy, table);
+        assert!(ArcLengthTable::from_lengths(vec![0.0]).is_none());
+        assert!(ArcLengthTable::from_lengths(vec![1.0, 2.0]).is_none());
+        assert!(ArcLengthTable::from_lengths(vec![0.0, 2.0, 1.0]).is_none());
+        assert!(ArcLengthTable::from_lengths(vec![0.0, f32::NAN]).is_none());
+    }
+
+    #[test]
+    fn test_sample_evenly() {
+        let spline = CatmullRom::new(sample_points()).unwrap();
+        let table = ArcLengthTable::new(&spline, 1024);
+        let points = table.sample_evenly(&spline, 11);
+
+        assert_eq!(points.len(), 11);
+        assert_abs_diff_eq!(points[0], sample_points()[0], 1e-6);
+        assert_abs_diff_eq!(points[10], sample_points()[4], 1e-4);
+
+        // Neighbouring points are roughly the same distance apart along the curve,
+        // chords are shorter than the arc between them around the sharper turns
+        let step = table.length() / 10.0;
+        for pair in points.windows(2) {
+            let chord = (pair[1] - pair[0]).length();
+            assert!(
+                chord <= step * 1.001 && chord > step * 0.85,
+                "{} {}",
+                chord,
+                step
+            );
+        }
+
+        assert!(table.sample_evenly(&spline, 0).is_empty());
+        assert_eq!(table.sample_evenly(&spline, 1), vec![sample_points()[0]]);
+    }
+}
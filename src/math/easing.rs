@@ -0,0 +1,195 @@
+// Easing functions for animation, see https://easings.net for plots.
+// All take t in [0, 1] and return 0 at t = 0 and 1 at t = 1. Elastic and back
+// overshoot outside of [0, 1] in between.
+use std::f32::consts::PI;
+
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+pub fn ease_in_quad(t: f32) -> f32 {
+    t * t
+}
+
+pub fn ease_out_quad(t: f32) -> f32 {
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+pub fn ease_in_out_quad(t: f32) -> f32 {
+    if t < 0.5 {
+        2.0 * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+    }
+}
+
+pub fn ease_in_cubic(t: f32) -> f32 {
+    t * t * t
+}
+
+pub fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+pub fn ease_in_out_cubic(t: f32) -> f32 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+const ELASTIC_PERIOD: f32 = 2.0 * PI / 3.0;
+const ELASTIC_IN_OUT_PERIOD: f32 = 2.0 * PI / 4.5;
+
+pub fn ease_in_elastic(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        return t.clamp(0.0, 1.0);
+    }
+
+    -(2.0f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * ELASTIC_PERIOD).sin()
+}
+
+pub fn ease_out_elastic(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        return t.clamp(0.0, 1.0);
+    }
+
+    2.0f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * ELASTIC_PERIOD).sin() + 1.0
+}
+
+pub fn ease_in_out_elastic(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        return t.clamp(0.0, 1.0);
+    }
+
+    let sin = ((20.0 * t - 11.125) * ELASTIC_IN_OUT_PERIOD).sin();
+
+    if t < 0.5 {
+        -(2.0f32.powf(20.0 * t - 10.0) * sin) / 2.0
+    } else {
+        2.0f32.powf(-20.0 * t + 10.0) * sin / 2.0 + 1.0
+    }
+}
+
+// How far back overshoots, 1.70158 gives a 10% overshoot
+const BACK: f32 = 1.70158;
+const BACK_IN_OUT: f32 = BACK * 1.525;
+
+pub fn ease_in_back(t: f32) -> f32 {
+    (BACK + 1.0) * t * t * t - BACK * t * t
+}
+
+pub fn ease_out_back(t: f32) -> f32 {
+    1.0 - ease_in_back(1.0 - t)
+}
+
+pub fn ease_in_out_back(t: f32) -> f32 {
+    if t < 0.5 {
+        (2.0 * t).powi(2) * ((BACK_IN_OUT + 1.0) * 2.0 * t - BACK_IN_OUT) / 2.0
+    } else {
+        ((2.0 * t - 2.0).powi(2) * ((BACK_IN_OUT + 1.0) * (t * 2.0 - 2.0) + BACK_IN_OUT) + 2.0)
+            / 2.0
+    }
+}
+
+pub fn ease_out_bounce(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+pub fn ease_in_bounce(t: f32) -> f32 {
+    1.0 - ease_out_bounce(1.0 - t)
+}
+
+pub fn ease_in_out_bounce(t: f32) -> f32 {
+    if t < 0.5 {
+        (1.0 - ease_out_bounce(1.0 - 2.0 * t)) / 2.0
+    } else {
+        (1.0 + ease_out_bounce(2.0 * t - 1.0)) / 2.0
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod easing_tests {
+    use crate::math::easing::*;
+
+    type Easing = (&'static str, fn(f32) -> f32);
+
+    const ALL: [Easing; 16] = [
+        ("linear", linear),
+        ("ease_in_quad", ease_in_quad),
+        ("ease_out_quad", ease_out_quad),
+        ("ease_in_out_quad", ease_in_out_quad),
+        ("ease_in_cubic", ease_in_cubic),
+        ("ease_out_cubic", ease_out_cubic),
+        ("ease_in_out_cubic", ease_in_out_cubic),
+        ("ease_in_elastic", ease_in_elastic),
+        ("ease_out_elastic", ease_out_elastic),
+        ("ease_in_out_elastic", ease_in_out_elastic),
+        ("ease_in_back", ease_in_back),
+        ("ease_out_back", ease_out_back),
+        ("ease_in_out_back", ease_in_out_back),
+        ("ease_in_bounce", ease_in_bounce),
+        ("ease_out_bounce", ease_out_bounce),
+        ("ease_in_out_bounce", ease_in_out_bounce),
+    ];
+
+    #[test]
+    fn test_endpoints() {
+        for (name, f) in ALL {
+            assert!(f(0.0).abs() < 1e-6, "{}(0) = {}", name, f(0.0));
+            assert!((f(1.0) - 1.0).abs() < 1e-6, "{}(1) = {}", name, f(1.0));
+        }
+    }
+
+    #[test]
+    fn test_in_out_are_symmetric() {
+        for (name, f) in ALL.iter().filter(|(name, _)| name.contains("in_out")) {
+            assert!((f(0.5) - 0.5).abs() < 1e-5, "{}(0.5) = {}", name, f(0.5));
+
+            for i in 0..=10 {
+                let t = i as f32 / 10.0;
+                assert!((f(t) + f(1.0 - t) - 1.0).abs() < 1e-5, "{} at {}", name, t);
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_values() {
+        assert_eq!(ease_in_quad(0.5), 0.25);
+        assert_eq!(ease_out_quad(0.5), 0.75);
+        assert_eq!(ease_in_cubic(0.5), 0.125);
+        assert_eq!(ease_out_cubic(0.5), 0.875);
+        assert_eq!(ease_out_bounce(1.0 / 2.75), 1.0);
+    }
+
+    #[test]
+    fn test_overshoot() {
+        // Back dips below 0 at the start, roughly 10%
+        let min = (1..100)
+            .map(|i| ease_in_back(i as f32 / 100.0))
+            .fold(f32::MAX, f32::min);
+        assert!(min < -0.09 && min > -0.11, "{}", min);
+
+        // Elastic overshoots past 1 on the way out
+        assert!((1..100).any(|i| ease_out_elastic(i as f32 / 100.0) > 1.0));
+
+        // Bounce never leaves [0, 1]
+        assert!((0..=100).all(|i| (0.0..=1.0).contains(&ease_out_bounce(i as f32 / 100.0))));
+    }
+}
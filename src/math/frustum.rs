@@ -0,0 +1,405 @@
+use crate::math::{Aabb, Matrix4, Plane, PlaneSide, Sphere, Vector3, Vector4};
+
+// The six planes of a view frustum in world space, normals point inwards.
+// Built from a view-projection matrix (Gribb/Hartmann), so any projection works.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Frustum {
+    // left, right, bottom, top, near, far
+    pub planes: [Plane; 6],
+}
+
+// One bit per object from the batch culling functions, set when the object is visible
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VisibilityMask {
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl Frustum {
+    pub const LEFT: usize = 0;
+    pub const RIGHT: usize = 1;
+    pub const BOTTOM: usize = 2;
+    pub const TOP: usize = 3;
+    pub const NEAR: usize = 4;
+    pub const FAR: usize = 5;
+
+    // For projections with NDC z in [-1, 1], like Matrix4::perspective and orthographic.
+    // @params: view_projection is projection * view, giving world space planes
+    pub fn from_matrix(view_projection: &Matrix4) -> Frustum {
+        let [x, y, z, w] = rows(view_projection);
+        Frustum::from_rows([w + x, w - x, w + y, w - y, w + z, w - z])
+    }
+
+    // For projections with NDC z in [0, 1] and near at 0 (DepthRange::ZeroToOne)
+    pub fn from_matrix_zero_to_one(view_projection: &Matrix4) -> Frustum {
+        let [x, y, z, w] = rows(view_projection);
+        Frustum::from_rows([w + x, w - x, w + y, w - y, z, w - z])
+    }
+
+    // For projections with NDC z in [0, 1] and near at 1 (DepthRange::ReversedZeroToOne),
+    // like Matrix4::perspective_reversed_z
+    pub fn from_matrix_reversed_z(view_projection: &Matrix4) -> Frustum {
+        let [x, y, z, w] = rows(view_projection);
+        Frustum::from_rows([w + x, w - x, w + y, w - y, w - z, z])
+    }
+
+    fn from_rows(rows: [Vector4; 6]) -> Frustum {
+        Frustum {
+            planes: rows.map(|row| {
+                // The far row of an infinite projection has no normal, the plane is at infinity
+                // and everything is in front of it
+                if row.xyz().length_squared() == 0.0 {
+                    return Plane::new(Vector3::zero(), f32::MAX);
+                }
+
+                Plane::new(row.xyz(), row.w).normalized()
+            }),
+        }
+    }
+
+    // Points on a plane count as inside
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(point) >= 0.0)
+    }
+
+    // Entirely inside
+    pub fn contains_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.classify_sphere(sphere) == PlaneSide::Front)
+    }
+
+    // Inside or partly inside. Conservative: spheres near a corner but outside can pass.
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.classify_sphere(sphere) != PlaneSide::Back)
+    }
+
+    // Entirely inside
+    pub fn contains_aabb(&self, aabb: &Aabb) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.classify_aabb(aabb) == PlaneSide::Front)
+    }
+
+    // Inside or partly inside. Conservative like intersects_sphere.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.classify_aabb(aabb) != PlaneSide::Back)
+    }
+
+    // Bit i is set if spheres[i] intersects the frustum
+    pub fn cull_spheres(&self, spheres: &[Sphere]) -> VisibilityMask {
+        VisibilityMask::from_fn(spheres.len(), |i| self.intersects_sphere(&spheres[i]))
+    }
+
+    // Bit i is set if aabbs[i] intersects the frustum
+    pub fn cull_aabbs(&self, aabbs: &[Aabb]) -> VisibilityMask {
+        VisibilityMask::from_fn(aabbs.len(), |i| self.intersects_aabb(&aabbs[i]))
+    }
+}
+
+fn rows(m: &Matrix4) -> [Vector4; 4] {
+    [0, 1, 2, 3].map(|i| Vector4::from_array(m.get_row(i)))
+}
+
+impl VisibilityMask {
+    fn from_fn(len: usize, mut visible: impl FnMut(usize) -> bool) -> VisibilityMask {
+        let mut bits = vec![0u64; len.div_ceil(64)];
+
+        for i in 0..len {
+            if visible(i) {
+                bits[i / 64] |= 1 << (i % 64);
+            }
+        }
+
+        VisibilityMask { bits, len }
+    }
+
+    // Inverse of as_words. None if there isn't exactly one word per 64 objects or a bit past
+    // len is set.
+    pub fn from_words(words: Vec<u64>, len: usize) -> Option<VisibilityMask> {
+        if words.len() != len.div_ceil(64) {
+            return None;
+        }
+        if !len.is_multiple_of(64) && words[words.len() - 1] >> (len % 64) != 0 {
+            return None;
+        }
+
+        Some(VisibilityMask { bits: words, len })
+    }
+
+    // Number of objects tested, not the number of visible ones
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_visible(&self, i: usize) -> bool {
+        if i >= self.len {
+            panic!("Index provided to VisibilityMask is out of bounds!");
+        }
+
+        self.bits[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    pub fn count_visible(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    // Indices of the visible objects, in order
+    pub fn visible_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|i| self.is_visible(*i))
+    }
+
+    // Bit i % 64 of word i / 64 is object i, unused high bits of the last word are 0
+    pub fn as_words(&self) -> &[u64] {
+        &self.bits
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod frustum_tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::assert_abs_diff_eq;
+    use crate::math::*;
+
+    // 90 degree fov camera at the origin looking down -z, near 1, far 100
+    fn sample_frustum() -> Frustum {
+        Frustum::from_matrix(&Matrix4::perspective(Rad(FRAC_PI_2), 1.0, 1.0, 100.0))
+    }
+
+    #[test]
+    fn test_planes_are_normalized() {
+        for plane in sample_frustum().planes {
+            assert_abs_diff_eq!(plane.normal.length(), 1.0, 1e-5);
+        }
+
+        let frustum = sample_frustum();
+        let near = frustum.planes[Frustum::NEAR];
+        let far = frustum.planes[Frustum::FAR];
+
+        assert_abs_diff_eq!(
+            near.signed_distance(Vector3::new(0.0, 0.0, -3.0)),
+            2.0,
+            1e-4
+        );
+        assert_abs_diff_eq!(
+            far.signed_distance(Vector3::new(0.0, 0.0, -90.0)),
+            10.0,
+            1e-3
+        );
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let frustum = sample_frustum();
+
+        assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -10.0)));
+        assert!(frustum.contains_point(Vector3::new(9.0, -9.0, -10.0)));
+        assert!(!frustum.contains_point(Vector3::new(11.0, 0.0, -10.0)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, -0.5)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, -101.0)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, 10.0)));
+    }
+
+    #[test]
+    fn test_spheres() {
+        let frustum = sample_frustum();
+
+        let inside = Sphere::new(Vector3::new(0.0, 0.0, -50.0), 1.0);
+        assert!(frustum.contains_sphere(&inside));
+        assert!(frustum.intersects_sphere(&inside));
+
+        // Straddles the right plane
+        let straddling = Sphere::new(Vector3::new(10.0, 0.0, -10.0), 1.0);
+        assert!(!frustum.contains_sphere(&straddling));
+        assert!(frustum.intersects_sphere(&straddling));
+
+        let behind = Sphere::new(Vector3::new(0.0, 0.0, 5.0), 1.0);
+        assert!(!frustum.intersects_sphere(&behind));
+    }
+
+    #[test]
+    fn test_aabbs() {
+        let frustum = sample_frustum();
+
+        let inside = Aabb::from_center_extents(Vector3::new(0.0, 0.0, -20.0), Vector3::splat(1.0));
+        assert!(frustum.contains_aabb(&inside));
+        assert!(frustum.intersects_aabb(&inside));
+
+        let straddling_far =
+            Aabb::from_center_extents(Vector3::new(0.0, 0.0, -100.0), Vector3::splat(1.0));
+        assert!(!frustum.contains_aabb(&straddling_far));
+        assert!(frustum.intersects_aabb(&straddling_far));
+
+        let left = Aabb::from_center_extents(Vector3::new(-30.0, 0.0, -10.0), Vector3::splat(1.0));
+        assert!(!frustum.intersects_aabb(&left));
+    }
+
+    #[test]
+    fn test_view_and_orthographic() {
+        // Camera at +x looking at the origin, the origin is visible and +x behind it isn't
+        let view = Matrix4::look_at_rh(
+            Vector3::new(20.0, 0.0, 0.0),
+            Vector3::zero(),
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+        let frustum =
+            Frustum::from_matrix(&(Matrix4::perspective(Rad(1.0), 1.5, 0.1, 50.0) * view));
+        assert!(frustum.contains_point(Vector3::zero()));
+        assert!(!frustum.contains_point(Vector3::new(30.0, 0.0, 0.0)));
+
+        let ortho = Frustum::from_matrix(&Matrix4::orthographic(-1.0, 1.0, -1.0, 1.0, 0.0, 10.0));
+        assert!(ortho.contains_point(Vector3::new(1.0, -1.0, -10.0)));
+        assert!(!ortho.contains_point(Vector3::new(1.1, 0.0, -5.0)));
+    }
+
+    #[test]
+    fn test_infinite_projections() {
+        let view = Matrix4::look_at_rh(
+            Vector3::new(0.0, 0.0, 5.0),
+            Vector3::zero(),
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+        let frustums = [
+            Frustum::from_matrix(&(Matrix4::perspective_infinite(Rad(FRAC_PI_2), 1.0, 1.0) * view)),
+            Frustum::from_matrix_reversed_z(
+                &(Matrix4::perspective_infinite_reversed_z(Rad(FRAC_PI_2), 1.0, 1.0) * view),
+            ),
+        ];
+
+        for frustum in frustums {
+            for plane in frustum.planes {
+                assert!(!plane.normal.x.is_nan() && plane.d.is_finite());
+            }
+
+            // Straight ahead at any distance, but not behind the near plane
+            assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -5.0)));
+            assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -1e6)));
+            assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, 4.5)));
+            assert!(!frustum.contains_point(Vector3::new(20.0, 0.0, -5.0)));
+
+            let far_away = Sphere::new(Vector3::new(0.0, 0.0, -1e4), 10.0);
+            assert!(frustum.contains_sphere(&far_away));
+            let mask = frustum.cull_spheres(&[far_away, Sphere::new(Vector3::splat(50.0), 1.0)]);
+            assert_eq!(mask.visible_indices().collect::<Vec<_>>(), vec![0]);
+        }
+    }
+
+    #[test]
+    fn test_reversed_z() {
+        let gl = sample_frustum();
+        let reversed = Frustum::from_matrix_reversed_z(&Matrix4::perspective_reversed_z(
+            Rad(FRAC_PI_2),
+            1.0,
+            1.0,
+            100.0,
+        ));
+
+        for point in [
+            Vector3::new(0.0, 0.0, -0.9),
+            Vector3::new(0.0, 0.0, -1.1),
+            Vector3::new(0.0, 0.0, -99.0),
+            Vector3::new(0.0, 0.0, -101.0),
+            Vector3::new(5.0, 5.0, -5.5),
+        ] {
+            assert_eq!(gl.contains_point(point), reversed.contains_point(point));
+        }
+
+        // Near and far keep their meaning, a point 2 in front of the near plane
+        let near = reversed.planes[Frustum::NEAR];
+        let far = reversed.planes[Frustum::FAR];
+        assert_abs_diff_eq!(
+            near.signed_distance(Vector3::new(0.0, 0.0, -3.0)),
+            2.0,
+            1e-4
+        );
+        assert_abs_diff_eq!(
+            far.signed_distance(Vector3::new(0.0, 0.0, -90.0)),
+            10.0,
+            1e-3
+        );
+    }
+
+    #[test]
+    fn test_zero_to_one_depth() {
+        // perspective() with NDC z remapped from [-1, 1] to [0, 1]
+        let remap = Matrix4::from_array([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.5, 0.0],
+            [0.0, 0.0, 0.5, 1.0],
+        ]);
+        let frustum = Frustum::from_matrix_zero_to_one(
+            &(remap * Matrix4::perspective(Rad(FRAC_PI_2), 1.0, 1.0, 100.0)),
+        );
+
+        let near = frustum.planes[Frustum::NEAR];
+        assert_abs_diff_eq!(
+            near.signed_distance(Vector3::new(0.0, 0.0, -3.0)),
+            2.0,
+            1e-4
+        );
+        assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -1.1)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, -0.9)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, -101.0)));
+    }
+
+    #[test]
+    fn test_batch_culling() {
+        let frustum = sample_frustum();
+
+        // Every third sphere is behind the camera, 70 spheres span two words
+        let spheres: Vec<Sphere> = (0..70)
+            .map(|i| {
+                let z = if i % 3 == 0 { 10.0 } else { -10.0 };
+                Sphere::new(Vector3::new(0.0, 0.0, z), 1.0)
+            })
+            .collect();
+
+        let mask = frustum.cull_spheres(&spheres);
+
+        assert_eq!(mask.len(), 70);
+        assert_eq!(mask.as_words().len(), 2);
+        assert_eq!(mask.count_visible(), 70 - 24);
+        for i in 0..70 {
+            assert_eq!(mask.is_visible(i), i % 3 != 0);
+        }
+        assert_eq!(
+            mask.visible_indices().take(3).collect::<Vec<_>>(),
+            vec![1, 2, 4]
+        );
+        assert_eq!(mask.as_words()[1] >> 6, 0);
+
+        let aabbs: Vec<Aabb> = spheres
+            .iter()
+            .map(|s| Aabb::from_center_extents(s.center, Vector3::splat(s.radius)))
+            .collect();
+        assert_eq!(frustum.cull_aabbs(&aabbs), mask);
+
+        assert!(frustum.cull_aabbs(&[]).is_empty());
+
+        let words = mask.as_words().to_vec();
+        assert_eq!(VisibilityMask::from_words(words.clone(), 70), Some(mask));
+        assert!(VisibilityMask::from_words(words.clone(), 64).is_none());
+        assert!(VisibilityMask::from_words(words, 68).is_none());
+        assert!(VisibilityMask::from_words(Vec::new(), 0)
+            .unwrap()
+            .is_empty());
+    }
+}
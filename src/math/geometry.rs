@@ -0,0 +1,883 @@
+use crate::math::{Matrix3, Matrix4, Vector3};
+
+// Below this a ray counts as parallel to a triangle or plane
+const PARALLEL_EPSILON: f32 = 1e-7;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ray {
+    pub origin: Vector3,
+    pub direction: Vector3,
+}
+
+// Points p where normal.dot(p) + d == 0, the normal points to the front side
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plane {
+    pub normal: Vector3,
+    pub d: f32,
+}
+
+// Which side of a plane a shape is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlaneSide {
+    Front,
+    Back,
+    Intersecting,
+}
+
+// Axis aligned bounding box
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aabb {
+    pub min: Vector3,
+    pub max: Vector3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sphere {
+    pub center: Vector3,
+    pub radius: f32,
+}
+
+// Counter clockwise winding is the front face, same as OpenGL
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangle {
+    pub a: Vector3,
+    pub b: Vector3,
+    pub c: Vector3,
+}
+
+// Oriented bounding box, the columns of axes are its normalized local x, y and z axes
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Obb {
+    pub center: Vector3,
+    pub axes: Matrix3,
+    pub half_extents: Vector3,
+}
+
+impl Ray {
+    // @params: direction should be normalized so t values are distances
+    pub fn new(origin: Vector3, direction: Vector3) -> Self {
+        Self { origin, direction }
+    }
+
+    pub fn at(&self, t: f32) -> Vector3 {
+        self.origin + self.direction * t
+    }
+
+    pub fn closest_point(&self, point: Vector3) -> Vector3 {
+        let t = (point - self.origin).dot(self.direction) / self.direction.length_squared();
+        self.at(t.max(0.0))
+    }
+
+    pub fn distance_to_point(&self, point: Vector3) -> f32 {
+        self.closest_point(point).distance(point)
+    }
+
+    // All intersect functions return the smallest t >= 0 where the ray hits, or None.
+    // A ray starting inside a solid shape hits at t = 0.
+
+    // Hits from both sides, returns None if the ray is parallel to the plane
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<f32> {
+        let denom = plane.normal.dot(self.direction);
+
+        if denom.abs() < PARALLEL_EPSILON {
+            return None;
+        }
+
+        let t = -plane.signed_distance(self.origin) / denom;
+        (t >= 0.0).then_some(t)
+    }
+
+    // Möller–Trumbore, hits both faces. Rays in the triangle's plane never hit.
+    pub fn intersect_triangle(&self, triangle: &Triangle) -> Option<f32> {
+        self.intersect_triangle_barycentric(triangle)
+            .map(|(t, _, _)| t)
+    }
+
+    // Same as intersect_triangle but also returns the barycentric (u, v) of the hit,
+    // the hit point is a * (1 - u - v) + b * u + c * v
+    pub fn intersect_triangle_barycentric(&self, triangle: &Triangle) -> Option<(f32, f32, f32)> {
+        let edge1 = triangle.b - triangle.a;
+        let edge2 = triangle.c - triangle.a;
+
+        let p = self.direction.cross(edge2);
+        let det = edge1.dot(p);
+
+        if det.abs() < PARALLEL_EPSILON {
+            return None;
+        }
+
+        let inv_det = 1.0 / det;
+        let s = self.origin - triangle.a;
+
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = s.cross(edge1);
+        let v = self.direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = edge2.dot(q) * inv_det;
+        (t >= 0.0).then_some((t, u, v))
+    }
+
+    // Slab test, surfaces and edges count as hits
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+        let mut t_min = 0.0f32;
+        let mut t_max = f32::INFINITY;
+
+        for i in 0..3 {
+            // Parallel to this slab, 1 / 0 would give NaN for a ray lying on a face
+            if self.direction[i] == 0.0 {
+                if self.origin[i] < aabb.min[i] || self.origin[i] > aabb.max[i] {
+                    return None;
+                }
+                continue;
+            }
+
+            let inv_dir = 1.0 / self.direction[i];
+            let t1 = (aabb.min[i] - self.origin[i]) * inv_dir;
+            let t2 = (aabb.max[i] - self.origin[i]) * inv_dir;
+
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+        }
+
+        (t_min <= t_max).then_some(t_min)
+    }
+
+    pub fn intersect_sphere(&self, sphere: &Sphere) -> Option<f32> {
+        let offset = self.origin - sphere.center;
+
+        let a = self.direction.length_squared();
+        let half_b = offset.dot(self.direction);
+        let c = offset.length_squared() - sphere.radius * sphere.radius;
+
+        // Starts inside
+        if c <= 0.0 {
+            return Some(0.0);
+        }
+
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0.0 || half_b > 0.0 {
+            return None;
+        }
+
+        Some((-half_b - discriminant.sqrt()) / a)
+    }
+
+    pub fn intersect_obb(&self, obb: &Obb) -> Option<f32> {
+        // In the box's local space the box is an aabb and t stays the same
+        let to_local = obb.axes.transpose();
+        let local = Ray::new(
+            to_local * (self.origin - obb.center),
+            to_local * self.direction,
+        );
+
+        local.intersect_aabb(&Aabb::new(-obb.half_extents, obb.half_extents))
+    }
+}
+
+impl Plane {
+    // @params: normal should be normalized so distances are real distances
+    pub fn new(normal: Vector3, d: f32) -> Self {
+        Self { normal, d }
+    }
+
+    pub fn from_point_normal(point: Vector3, normal: Vector3) -> Plane {
+        Plane::new(normal, -normal.dot(point))
+    }
+
+    // Counter clockwise points see the front side. Returns None if the points are on a line.
+    pub fn from_points(a: Vector3, b: Vector3, c: Vector3) -> Option<Plane> {
+        let normal = (b - a).cross(c - a);
+        let length = normal.length();
+
+        if length == 0.0 {
+            return None;
+        }
+
+        Some(Plane::from_point_normal(a, normal / length))
+    }
+
+    // Scales the normal to length 1, d scales with it so the plane stays the same
+    pub fn normalized(self) -> Plane {
+        let length = self.normal.length();
+        Plane::new(self.normal / length, self.d / length)
+    }
+
+    // Positive in front of the plane
+    pub fn signed_distance(&self, point: Vector3) -> f32 {
+        self.normal.dot(point) + self.d
+    }
+
+    pub fn closest_point(&self, point: Vector3) -> Vector3 {
+        point - self.normal * (self.signed_distance(point) / self.normal.length_squared())
+    }
+
+    pub fn classify_point(&self, point: Vector3) -> PlaneSide {
+        let distance = self.signed_distance(point);
+
+        if distance > 0.0 {
+            PlaneSide::Front
+        } else if distance < 0.0 {
+            PlaneSide::Back
+        } else {
+            PlaneSide::Intersecting
+        }
+    }
+
+    pub fn classify_sphere(&self, sphere: &Sphere) -> PlaneSide {
+        let distance = self.signed_distance(sphere.center);
+
+        if distance > sphere.radius {
+            PlaneSide::Front
+        } else if distance < -sphere.radius {
+            PlaneSide::Back
+        } else {
+            PlaneSide::Intersecting
+        }
+    }
+
+    // Touching the plane counts as intersecting
+    pub fn classify_aabb(&self, aabb: &Aabb) -> PlaneSide {
+        // Projected half size of the box onto the normal
+        let radius = aabb.extents().dot(self.normal.abs());
+        self.classify_sphere(&Sphere::new(aabb.center(), radius))
+    }
+}
+
+impl Aabb {
+    pub fn new(min: Vector3, max: Vector3) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_center_extents(center: Vector3, extents: Vector3) -> Aabb {
+        Aabb::new(center - extents, center + extents)
+    }
+
+    // Returns None for an empty slice
+    pub fn from_points(points: &[Vector3]) -> Option<Aabb> {
+        let (first, rest) = points.split_first()?;
+
+        Some(rest.iter().fold(Aabb::new(*first, *first), |aabb, point| {
+            Aabb::new(aabb.min.min(*point), aabb.max.max(*point))
+        }))
+    }
+
+    pub fn center(&self) -> Vector3 {
+        (self.min + self.max) * 0.5
+    }
+
+    // Half the size on each axis
+    pub fn extents(&self) -> Vector3 {
+        (self.max - self.min) * 0.5
+    }
+
+    pub fn size(&self) -> Vector3 {
+        self.max - self.min
+    }
+
+    // Points on the surface count as inside
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        (0..3).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    // Touching boxes intersect
+    pub fn intersects_aabb(&self, other: &Aabb) -> bool {
+        (0..3).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        sphere.intersects_aabb(self)
+    }
+
+    pub fn intersects_plane(&self, plane: &Plane) -> bool {
+        plane.classify_aabb(self) == PlaneSide::Intersecting
+    }
+
+    pub fn closest_point(&self, point: Vector3) -> Vector3 {
+        point.clamp(self.min, self.max)
+    }
+
+    // Zero for points inside
+    pub fn distance_to_point(&self, point: Vector3) -> f32 {
+        self.closest_point(point).distance(point)
+    }
+
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    // Smallest aabb around the transformed box (Arvo's method).
+    // @params: m must be affine, the bottom row is ignored
+    pub fn transform(&self, m: &Matrix4) -> Aabb {
+        let basis = Matrix3::from_matrix4(*m);
+        let center = basis * self.center() + m.w.xyz();
+
+        // Every column contributes its absolute value scaled by the extents
+        let extents = self.extents();
+        let abs_basis = Matrix3::from_columns(basis.x.abs(), basis.y.abs(), basis.z.abs());
+
+        Aabb::from_center_extents(center, abs_basis * extents)
+    }
+}
+
+impl Sphere {
+    pub fn new(center: Vector3, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        (point - self.center).length_squared() <= self.radius * self.radius
+    }
+
+    pub fn intersects_sphere(&self, other: &Sphere) -> bool {
+        let radius = self.radius + other.radius;
+        (other.center - self.center).length_squared() <= radius * radius
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.contains_point(aabb.closest_point(self.center))
+    }
+
+    pub fn intersects_plane(&self, plane: &Plane) -> bool {
+        plane.classify_sphere(self) == PlaneSide::Intersecting
+    }
+
+    // Distance to the surface, negative inside
+    pub fn distance_to_point(&self, point: Vector3) -> f32 {
+        self.center.distance(point) - self.radius
+    }
+}
+
+impl Triangle {
+    pub fn new(a: Vector3, b: Vector3, c: Vector3) -> Self {
+        Self { a, b, c }
+    }
+
+    // Not normalized, the length is twice the area
+    pub fn normal(&self) -> Vector3 {
+        (self.b - self.a).cross(self.c - self.a)
+    }
+
+    pub fn area(&self) -> f32 {
+        self.normal().length() * 0.5
+    }
+
+    // Returns None for a degenerate triangle
+    pub fn plane(&self) -> Option<Plane> {
+        Plane::from_points(self.a, self.b, self.c)
+    }
+
+    // Closest point on the triangle, from Real-Time Collision Detection 5.1.5
+    pub fn closest_point(&self, point: Vector3) -> Vector3 {
+        let (a, b, c) = (self.a, self.b, self.c);
+        let ab = b - a;
+        let ac = c - a;
+
+        let ap = point - a;
+        let d1 = ab.dot(ap);
+        let d2 = ac.dot(ap);
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return a;
+        }
+
+        let bp = point - b;
+        let d3 = ab.dot(bp);
+        let d4 = ac.dot(bp);
+        if d3 >= 0.0 && d4 <= d3 {
+            return b;
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        let cp = point - c;
+        let d5 = ab.dot(cp);
+        let d6 = ac.dot(cp);
+        if d6 >= 0.0 && d5 <= d6 {
+            return c;
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        // Inside the face
+        let denom = 1.0 / (va + vb + vc);
+        a + ab * (vb * denom) + ac * (vc * denom)
+    }
+
+    pub fn distance_to_point(&self, point: Vector3) -> f32 {
+        self.closest_point(point).distance(point)
+    }
+}
+
+impl Obb {
+    // @params: the columns of axes must be normalized and perpendicular
+    pub fn new(center: Vector3, axes: Matrix3, half_extents: Vector3) -> Self {
+        Self {
+            center,
+            axes,
+            half_extents,
+        }
+    }
+
+    // The aabb moved by a rotation, scale and translation matrix, without shear
+    pub fn from_aabb(aabb: &Aabb, m: &Matrix4) -> Obb {
+        let basis = Matrix3::from_matrix4(*m);
+        let scale = Vector3::new(basis.x.length(), basis.y.length(), basis.z.length());
+
+        Obb::new(
+            (*m * aabb.center().extend(1.0)).xyz(),
+            Matrix3::from_columns(basis.x / scale.x, basis.y / scale.y, basis.z / scale.z),
+            aabb.extents() * scale,
+        )
+    }
+
+    // The point in box space, where the box is centered at the origin with axis aligned sides
+    fn local_point(&self, point: Vector3) -> Vector3 {
+        self.axes.transpose() * (point - self.center)
+    }
+
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        let local = self.local_point(point).abs();
+        (0..3).all(|i| local[i] <= self.half_extents[i])
+    }
+
+    pub fn closest_point(&self, point: Vector3) -> Vector3 {
+        let local = self
+            .local_point(point)
+            .clamp(-self.half_extents, self.half_extents);
+
+        self.center + self.axes * local
+    }
+
+    pub fn distance_to_point(&self, point: Vector3) -> f32 {
+        self.closest_point(point).distance(point)
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        sphere.contains_point(self.closest_point(sphere.center))
+    }
+
+    // Smallest aabb around the box
+    pub fn bounding_aabb(&self) -> Aabb {
+        let abs_axes =
+            Matrix3::from_columns(self.axes.x.abs(), self.axes.y.abs(), self.axes.z.abs());
+        Aabb::from_center_extents(self.center, abs_axes * self.half_extents)
+    }
+
+    // Separating axis test with the 15 candidate axes
+    pub fn intersects_obb(&self, other: &Obb) -> bool {
+        let a = [self.axes.x, self.axes.y, self.axes.z];
+        let b = [other.axes.x, other.axes.y, other.axes.z];
+        let offset = other.center - self.center;
+
+        let separated_on = |axis: Vector3| {
+            // Parallel edges give a zero cross product, that axis separates nothing
+            if axis.length_squared() < PARALLEL_EPSILON {
+                return false;
+            }
+
+            let project = |axes: &[Vector3; 3], half_extents: Vector3| {
+                (0..3)
+                    .map(|i| axes[i].dot(axis).abs() * half_extents[i])
+                    .sum::<f32>()
+            };
+
+            offset.dot(axis).abs()
+                > project(&a, self.half_extents) + project(&b, other.half_extents)
+        };
+
+        let face_axes = a.iter().chain(b.iter()).copied();
+        let edge_axes = a
+            .iter()
+            .flat_map(|a_axis| b.iter().map(move |b_axis| a_axis.cross(*b_axis)));
+
+        !face_axes.chain(edge_axes).any(separated_on)
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod geometry_tests {
+    use crate::assert_abs_diff_eq;
+    use crate::math::*;
+
+    fn unit_box() -> Aabb {
+        Aabb::new(Vector3::splat(-1.0), Vector3::splat(1.0))
+    }
+
+    fn sample_triangle() -> Triangle {
+        Triangle::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn test_ray_triangle_hit() {
+        let ray = Ray::new(Vector3::new(0.25, 0.25, 5.0), Vector3::new(0.0, 0.0, -1.0));
+        let (t, u, v) = ray
+            .intersect_triangle_barycentric(&sample_triangle())
+            .unwrap();
+
+        assert_eq!(t, 5.0);
+        assert_eq!((u, v), (0.25, 0.25));
+
+        // Back face hits too
+        let ray = Ray::new(Vector3::new(0.25, 0.25, -5.0), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(ray.intersect_triangle(&sample_triangle()), Some(5.0));
+    }
+
+    #[test]
+    fn test_ray_triangle_edges_and_misses() {
+        let down = Vector3::new(0.0, 0.0, -1.0);
+
+        // Exactly on a vertex and on the hypotenuse
+        let ray = Ray::new(Vector3::new(0.0, 0.0, 1.0), down);
+        assert_eq!(ray.intersect_triangle(&sample_triangle()), Some(1.0));
+        let ray = Ray::new(Vector3::new(0.5, 0.5, 1.0), down);
+        assert_eq!(ray.intersect_triangle(&sample_triangle()), Some(1.0));
+
+        // Just outside the hypotenuse
+        let ray = Ray::new(Vector3::new(0.51, 0.5, 1.0), down);
+        assert_eq!(ray.intersect_triangle(&sample_triangle()), None);
+
+        // Behind the origin
+        let ray = Ray::new(Vector3::new(0.25, 0.25, 1.0), -down);
+        assert_eq!(ray.intersect_triangle(&sample_triangle()), None);
+
+        // Parallel, in the triangle's plane
+        let ray = Ray::new(Vector3::new(-1.0, 0.25, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(ray.intersect_triangle(&sample_triangle()), None);
+
+        // Degenerate triangle
+        let line = Triangle::new(
+            Vector3::zero(),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 0.0),
+        );
+        let ray = Ray::new(Vector3::new(0.5, 0.0, 1.0), down);
+        assert_eq!(ray.intersect_triangle(&line), None);
+    }
+
+    #[test]
+    fn test_ray_aabb() {
+        let ray = Ray::new(Vector3::new(-5.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some(4.0));
+
+        // Pointing away
+        let ray = Ray::new(Vector3::new(-5.0, 0.0, 0.0), Vector3::new(-1.0, 0.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), None);
+
+        // Starting inside
+        let ray = Ray::new(Vector3::zero(), Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some(0.0));
+
+        // Diagonal
+        let ray = Ray::new(Vector3::splat(-3.0), Vector3::splat(1.0).normalized());
+        let t = ray.intersect_aabb(&unit_box()).unwrap();
+        assert_abs_diff_eq!(ray.at(t), Vector3::splat(-1.0), 1e-5);
+    }
+
+    #[test]
+    fn test_ray_aabb_parallel_to_slab() {
+        // Parallel to the x slabs, outside of them
+        let ray = Ray::new(Vector3::new(2.0, -5.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), None);
+
+        // Parallel and exactly on the face, 0 * inf would be NaN
+        let ray = Ray::new(Vector3::new(1.0, -5.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some(4.0));
+
+        // Grazing an edge
+        let ray = Ray::new(Vector3::new(1.0, -5.0, 1.0), Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some(4.0));
+    }
+
+    #[test]
+    fn test_ray_plane_and_sphere() {
+        let plane = Plane::new(Vector3::new(0.0, 1.0, 0.0), -2.0);
+        let ray = Ray::new(Vector3::zero(), Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(ray.intersect_plane(&plane), Some(2.0));
+
+        let parallel = Ray::new(Vector3::zero(), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(parallel.intersect_plane(&plane), None);
+
+        let sphere = Sphere::new(Vector3::new(0.0, 0.0, -10.0), 2.0);
+        let ray = Ray::new(Vector3::zero(), Vector3::new(0.0, 0.0, -1.0));
+        assert_eq!(ray.intersect_sphere(&sphere), Some(8.0));
+
+        let away = Ray::new(Vector3::zero(), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(away.intersect_sphere(&sphere), None);
+
+        let inside = Ray::new(sphere.center, Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(inside.intersect_sphere(&sphere), Some(0.0));
+
+        // Tangent
+        let tangent = Ray::new(Vector3::new(2.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0));
+        assert_eq!(tangent.intersect_sphere(&sphere), Some(10.0));
+    }
+
+    #[test]
+    fn test_sphere_aabb() {
+        let aabb = unit_box();
+
+        assert!(Sphere::new(Vector3::zero(), 0.1).intersects_aabb(&aabb));
+        assert!(Sphere::new(Vector3::new(2.0, 0.0, 0.0), 1.0).intersects_aabb(&aabb));
+        assert!(!Sphere::new(Vector3::new(2.0, 0.0, 0.0), 0.99).intersects_aabb(&aabb));
+
+        // Near a corner the distance is diagonal, not per axis
+        assert!(!Sphere::new(Vector3::new(1.8, 1.8, 0.0), 1.0).intersects_aabb(&aabb));
+        assert!(Sphere::new(Vector3::new(1.5, 1.5, 0.0), 1.0).intersects_aabb(&aabb));
+    }
+
+    #[test]
+    fn test_aabb_plane() {
+        let aabb = unit_box();
+        let up = Vector3::new(0.0, 1.0, 0.0);
+
+        assert_eq!(
+            Plane::new(up, 0.0).classify_aabb(&aabb),
+            PlaneSide::Intersecting
+        );
+        assert_eq!(Plane::new(up, 2.0).classify_aabb(&aabb), PlaneSide::Front);
+        assert_eq!(Plane::new(up, -2.0).classify_aabb(&aabb), PlaneSide::Back);
+
+        // Touching counts as intersecting
+        assert!(aabb.intersects_plane(&Plane::new(up, 1.0)));
+
+        // Tilted plane reaches the corner
+        let diagonal =
+            Plane::from_point_normal(Vector3::splat(1.0), Vector3::splat(1.0).normalized());
+        assert!(aabb.intersects_plane(&diagonal));
+        let past = Plane::from_point_normal(Vector3::splat(1.01), Vector3::splat(1.0).normalized());
+        assert_eq!(past.classify_aabb(&aabb), PlaneSide::Back);
+    }
+
+    #[test]
+    fn test_plane_from_points() {
+        let plane = Plane::from_points(
+            Vector3::new(0.0, 3.0, 0.0),
+            Vector3::new(0.0, 3.0, 1.0),
+            Vector3::new(1.0, 3.0, 0.0),
+        )
+        .unwrap();
+
+        assert_abs_diff_eq!(plane.normal, Vector3::new(0.0, 1.0, 0.0), 1e-5);
+        assert_eq!(plane.signed_distance(Vector3::new(5.0, 5.0, 5.0)), 2.0);
+        assert_abs_diff_eq!(
+            plane.closest_point(Vector3::new(1.0, 7.0, 2.0)),
+            Vector3::new(1.0, 3.0, 2.0),
+            1e-5
+        );
+
+        let collinear =
+            Plane::from_points(Vector3::zero(), Vector3::splat(1.0), Vector3::splat(2.0));
+        assert_eq!(collinear, None);
+
+        let scaled = Plane::new(Vector3::new(0.0, 2.0, 0.0), -4.0).normalized();
+        assert_eq!(scaled, Plane::new(Vector3::new(0.0, 1.0, 0.0), -2.0));
+    }
+
+    #[test]
+    fn test_aabb_transform() {
+        let aabb = Aabb::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 1.0, 1.0));
+
+        let trans = Matrix4::create_translation(Matrix4::identity(), Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(
+            aabb.transform(&trans),
+            Aabb::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(3.0, 3.0, 4.0))
+        );
+
+        // 90 degrees about z swaps the x and y sizes
+        let mut rotated = Quaternion::from_axis_angle(
+            Vector3::new(0.0, 0.0, 1.0),
+            Rad(std::f32::consts::FRAC_PI_2),
+        )
+        .to_matrix4();
+        rotated.w = Vector4::new(0.0, 0.0, 0.0, 1.0);
+        let result = aabb.transform(&rotated);
+        assert_abs_diff_eq!(result.min, Vector3::new(-1.0, 0.0, 0.0), 1e-5);
+        assert_abs_diff_eq!(result.max, Vector3::new(0.0, 2.0, 1.0), 1e-5);
+
+        // Must match the box around the 8 transformed corners
+        let m = Transform::new(
+            Vector3::new(-1.0, 0.5, 2.0),
+            Quaternion::from_axis_angle(Vector3::new(0.6, 0.0, 0.8), Rad(0.7)),
+            Vector3::new(1.0, 2.0, 0.5),
+        )
+        .to_matrix();
+        let corners: Vec<Vector3> = (0..8)
+            .map(|i| {
+                let corner = Vector3::new(
+                    if i & 1 == 0 { aabb.min.x } else { aabb.max.x },
+                    if i & 2 == 0 { aabb.min.y } else { aabb.max.y },
+                    if i & 4 == 0 { aabb.min.z } else { aabb.max.z },
+                );
+                (m * corner.extend(1.0)).xyz()
+            })
+            .collect();
+        let expected = Aabb::from_points(&corners).unwrap();
+        let result = aabb.transform(&m);
+        assert_abs_diff_eq!(result.min, expected.min, 1e-5);
+        assert_abs_diff_eq!(result.max, expected.max, 1e-5);
+    }
+
+    #[test]
+    fn test_aabb_queries() {
+        let aabb = unit_box();
+
+        assert!(aabb.contains_point(Vector3::new(1.0, 1.0, 1.0)));
+        assert!(!aabb.contains_point(Vector3::new(1.0, 1.01, 1.0)));
+        assert_eq!(aabb.distance_to_point(Vector3::new(4.0, 0.0, 0.0)), 3.0);
+        assert_eq!(aabb.distance_to_point(Vector3::zero()), 0.0);
+
+        let touching = Aabb::new(Vector3::new(1.0, -1.0, -1.0), Vector3::new(2.0, 1.0, 1.0));
+        assert!(aabb.intersects_aabb(&touching));
+        let apart = Aabb::new(Vector3::new(1.1, -1.0, -1.0), Vector3::new(2.0, 1.0, 1.0));
+        assert!(!aabb.intersects_aabb(&apart));
+
+        assert_eq!(Aabb::from_points(&[]), None);
+    }
+
+    #[test]
+    fn test_triangle_closest_point() {
+        let triangle = sample_triangle();
+
+        // Each voronoi region
+        assert_abs_diff_eq!(
+            triangle.closest_point(Vector3::new(-1.0, -1.0, 0.0)),
+            triangle.a,
+            1e-5
+        );
+        assert_abs_diff_eq!(
+            triangle.closest_point(Vector3::new(2.0, -0.5, 0.0)),
+            triangle.b,
+            1e-5
+        );
+        assert_abs_diff_eq!(
+            triangle.closest_point(Vector3::new(-0.5, 2.0, 0.0)),
+            triangle.c,
+            1e-5
+        );
+        assert_abs_diff_eq!(
+            triangle.closest_point(Vector3::new(0.5, -1.0, 0.0)),
+            Vector3::new(0.5, 0.0, 0.0),
+            1e-5
+        );
+        assert_abs_diff_eq!(
+            triangle.closest_point(Vector3::new(-1.0, 0.5, 0.0)),
+            Vector3::new(0.0, 0.5, 0.0),
+            1e-5
+        );
+        assert_abs_diff_eq!(
+            triangle.closest_point(Vector3::new(1.0, 1.0, 0.0)),
+            Vector3::new(0.5, 0.5, 0.0),
+            1e-5
+        );
+        assert_abs_diff_eq!(
+            triangle.closest_point(Vector3::new(0.25, 0.25, 3.0)),
+            Vector3::new(0.25, 0.25, 0.0),
+            1e-5
+        );
+
+        assert_eq!(
+            triangle.distance_to_point(Vector3::new(0.25, 0.25, -3.0)),
+            3.0
+        );
+        assert_eq!(triangle.area(), 0.5);
+    }
+
+    #[test]
+    fn test_obb() {
+        let rotation = Quaternion::from_axis_angle(
+            Vector3::new(0.0, 0.0, 1.0),
+            Rad(std::f32::consts::FRAC_PI_4),
+        );
+        let obb = Obb::new(
+            Vector3::zero(),
+            rotation.to_matrix3(),
+            Vector3::new(2.0, 0.5, 1.0),
+        );
+
+        // Along the rotated x axis
+        assert!(obb.contains_point(Vector3::new(1.4, 1.4, 0.0)));
+        assert!(!obb.contains_point(Vector3::new(1.4, -1.4, 0.0)));
+        assert!((obb.distance_to_point(Vector3::new(0.0, 0.0, 3.0)) - 2.0).abs() < 1e-5);
+
+        let ray = Ray::new(
+            Vector3::new(-10.0, -10.0, 0.0),
+            Vector3::splat(1.0).xy().extend(0.0).normalized(),
+        );
+        let t = ray.intersect_obb(&obb).unwrap();
+        assert!((t - (200.0f32.sqrt() - 2.0)).abs() < 1e-4);
+
+        assert!(obb.intersects_sphere(&Sphere::new(Vector3::new(2.0, 2.0, 0.0), 1.0)));
+        assert!(!obb.intersects_sphere(&Sphere::new(Vector3::new(2.0, -2.0, 0.0), 1.0)));
+
+        let bounds = obb.bounding_aabb();
+        assert!(bounds.contains_point(Vector3::new(1.4, 1.4, 0.0)));
+    }
+
+    #[test]
+    fn test_obb_obb() {
+        let a = Obb::new(Vector3::zero(), Matrix3::identity(), Vector3::splat(1.0));
+
+        let rotated = Quaternion::from_axis_angle(
+            Vector3::new(0.0, 0.0, 1.0),
+            Rad(std::f32::consts::FRAC_PI_4),
+        )
+        .to_matrix3();
+
+        // Corner of b reaches 3 - sqrt(2) = 1.59 on x, so it doesn't touch a
+        let b = Obb::new(Vector3::new(3.0, 0.0, 0.0), rotated, Vector3::splat(1.0));
+        assert!(!a.intersects_obb(&b));
+
+        let b = Obb::new(Vector3::new(2.3, 0.0, 0.0), rotated, Vector3::splat(1.0));
+        assert!(a.intersects_obb(&b));
+
+        // Only separated by an edge-edge axis
+        let edge = Quaternion::from_axis_angle(
+            Vector3::new(1.0, 1.0, 0.0).normalized(),
+            Rad(std::f32::consts::FRAC_PI_4),
+        )
+        .to_matrix3();
+        let b = Obb::new(Vector3::new(1.5, 0.0, 2.5), edge, Vector3::splat(1.0));
+        assert!(!a.intersects_obb(&b));
+
+        // From a matrix
+        let m = Transform::new(
+            Vector3::new(0.0, 5.0, 0.0),
+            Quaternion::identity(),
+            Vector3::splat(2.0),
+        )
+        .to_matrix();
+        let moved = Obb::from_aabb(&Aabb::new(Vector3::splat(-1.0), Vector3::splat(1.0)), &m);
+        assert_eq!(moved.half_extents, Vector3::splat(2.0));
+        assert!(moved.contains_point(Vector3::new(0.0, 3.0, 0.0)));
+        assert!(!moved.contains_point(Vector3::new(0.0, 2.9, 0.0)));
+    }
+}
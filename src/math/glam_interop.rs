@@ -0,0 +1,119 @@
+// Conversions to and from the glam types with the same scalar and size
+
+use crate::math::{Matrix, Quaternion, Vector};
+
+macro_rules! impl_glam_vector {
+    ($($t:ty, $n:literal => $glam:ident),*) => {$(
+        impl From<glam::$glam> for Vector<$t, $n> {
+            fn from(v: glam::$glam) -> Self {
+                Vector(v.to_array())
+            }
+        }
+
+        impl From<Vector<$t, $n>> for glam::$glam {
+            fn from(v: Vector<$t, $n>) -> Self {
+                glam::$glam::from_array(v.0)
+            }
+        }
+    )*};
+}
+
+impl_glam_vector! {
+    f32, 2 => Vec2, f32, 3 => Vec3, f32, 4 => Vec4,
+    f64, 2 => DVec2, f64, 3 => DVec3, f64, 4 => DVec4,
+    i32, 2 => IVec2, i32, 3 => IVec3, i32, 4 => IVec4,
+    u32, 2 => UVec2, u32, 3 => UVec3, u32, 4 => UVec4
+}
+
+// glam is column-major too, so the column arrays go across unchanged
+macro_rules! impl_glam_matrix {
+    ($($t:ty, $n:literal => $glam:ident),*) => {$(
+        impl From<glam::$glam> for Matrix<$t, $n, $n> {
+            fn from(m: glam::$glam) -> Self {
+                Matrix::from_array(m.to_cols_array_2d())
+            }
+        }
+
+        impl From<Matrix<$t, $n, $n>> for glam::$glam {
+            fn from(m: Matrix<$t, $n, $n>) -> Self {
+                glam::$glam::from_cols_array_2d(&m.as_array())
+            }
+        }
+    )*};
+}
+
+impl_glam_matrix! {
+    f32, 2 => Mat2, f32, 3 => Mat3, f32, 4 => Mat4,
+    f64, 2 => DMat2, f64, 3 => DMat3, f64, 4 => DMat4
+}
+
+impl From<glam::Quat> for Quaternion {
+    fn from(q: glam::Quat) -> Self {
+        let [x, y, z, w] = q.to_array();
+        Quaternion::new(x, y, z, w)
+    }
+}
+
+impl From<Quaternion> for glam::Quat {
+    fn from(q: Quaternion) -> Self {
+        glam::Quat::from_xyzw(q.x, q.y, q.z, q.w)
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod glam_interop_tests {
+    use crate::math::*;
+
+    #[test]
+    fn test_vector_round_trip() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        let g: glam::Vec3 = v.into();
+        assert_eq!(g, glam::Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(Vector3::from(g), v);
+
+        let v = Vector2d::new(0.5, -1.5);
+        assert_eq!(Vector2d::from(glam::DVec2::from(v)), v);
+
+        let v = Vector4i::new(1, -2, 3, -4);
+        assert_eq!(Vector4i::from(glam::IVec4::from(v)), v);
+
+        let v = Vector3u::new(1, 2, 3);
+        assert_eq!(Vector3u::from(glam::UVec3::from(v)), v);
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let m = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0));
+        let g: glam::Mat4 = m.into();
+        assert_eq!(
+            g,
+            glam::Mat4::from_translation(glam::Vec3::new(1.0, 2.0, 3.0))
+        );
+        assert_eq!(Matrix4::from(g), m);
+
+        // Products agree in both directions
+        let a = Matrix4::perspective(Deg(60.0), 1.5, 0.1, 100.0);
+        let product = glam::Mat4::from(a) * g;
+        assert!(Matrix4::from(product).approx_eq(&(a * m)));
+
+        let m = Matrix3d::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        assert_eq!(glam::DMat3::from(m).y_axis, glam::DVec3::new(4.0, 5.0, 6.0));
+        assert_eq!(Matrix3d::from(glam::DMat3::from(m)), m);
+
+        let m = Matrix2::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(Matrix2::from(glam::Mat2::from(m)), m);
+    }
+
+    #[test]
+    fn test_quaternion_round_trip() {
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), Deg(90.0));
+        let g: glam::Quat = q.into();
+        assert_eq!(Quaternion::from(g), q);
+
+        // Both rotate the same way
+        let rotated = g * glam::Vec3::X;
+        let expected = q.rotate_vector(Vector3::new(1.0, 0.0, 0.0));
+        assert!((Vector3::from(rotated) - expected).length() < 1e-6);
+    }
+}
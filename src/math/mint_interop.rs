@@ -0,0 +1,124 @@
+// Conversions to and from the mint types, which most math crates understand
+
+use crate::math::{Matrix, Quaternion, Vector};
+
+macro_rules! impl_mint_vector {
+    ($($n:literal => $mint:ident),*) => {$(
+        impl<T> From<mint::$mint<T>> for Vector<T, $n> {
+            fn from(v: mint::$mint<T>) -> Self {
+                Vector(v.into())
+            }
+        }
+
+        impl<T> From<Vector<T, $n>> for mint::$mint<T> {
+            fn from(v: Vector<T, $n>) -> Self {
+                v.0.into()
+            }
+        }
+
+        impl<T> mint::IntoMint for Vector<T, $n> {
+            type MintType = mint::$mint<T>;
+        }
+    )*};
+}
+
+impl_mint_vector! { 2 => Vector2, 3 => Vector3, 4 => Vector4 }
+
+// Both sides are column-major, so columns map straight across
+macro_rules! impl_mint_matrix {
+    ($($n:literal => $mint:ident { $($field:ident),* }),*) => {$(
+        impl<T> From<mint::$mint<T>> for Matrix<T, $n, $n> {
+            fn from(m: mint::$mint<T>) -> Self {
+                Matrix([$(Vector::from(m.$field)),*])
+            }
+        }
+
+        impl<T> From<Matrix<T, $n, $n>> for mint::$mint<T> {
+            fn from(m: Matrix<T, $n, $n>) -> Self {
+                let [$($field),*] = m.0;
+                mint::$mint { $($field: $field.into()),* }
+            }
+        }
+
+        impl<T> mint::IntoMint for Matrix<T, $n, $n> {
+            type MintType = mint::$mint<T>;
+        }
+    )*};
+}
+
+impl_mint_matrix! {
+    2 => ColumnMatrix2 { x, y },
+    3 => ColumnMatrix3 { x, y, z },
+    4 => ColumnMatrix4 { x, y, z, w }
+}
+
+impl From<mint::Quaternion<f32>> for Quaternion {
+    fn from(q: mint::Quaternion<f32>) -> Self {
+        Quaternion::new(q.v.x, q.v.y, q.v.z, q.s)
+    }
+}
+
+impl From<Quaternion> for mint::Quaternion<f32> {
+    fn from(q: Quaternion) -> Self {
+        mint::Quaternion {
+            v: mint::Vector3 {
+                x: q.x,
+                y: q.y,
+                z: q.z,
+            },
+            s: q.w,
+        }
+    }
+}
+
+impl mint::IntoMint for Quaternion {
+    type MintType = mint::Quaternion<f32>;
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod mint_interop_tests {
+    use crate::math::*;
+
+    #[test]
+    fn test_vector_round_trip() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        let m: mint::Vector3<f32> = v.into();
+        assert_eq!((m.x, m.y, m.z), (1.0, 2.0, 3.0));
+        assert_eq!(Vector3::from(m), v);
+
+        let v = Vector2i::new(-4, 5);
+        assert_eq!(Vector2i::from(mint::Vector2::from(v)), v);
+
+        let v = Vector4d::new(1.0, -2.0, 3.5, 0.25);
+        assert_eq!(Vector4d::from(mint::Vector4::from(v)), v);
+
+        let v = Vector3u::new(7, 8, 9);
+        assert_eq!(Vector3u::from(mint::Vector3::from(v)), v);
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let m = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0));
+        let mint_m: mint::ColumnMatrix4<f32> = m.into();
+        // Translation lives in the last column
+        assert_eq!((mint_m.w.x, mint_m.w.y, mint_m.w.z), (1.0, 2.0, 3.0));
+        assert_eq!(Matrix4::from(mint_m), m);
+
+        let m = Matrix3d::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        let mint_m = mint::ColumnMatrix3::from(m);
+        assert_eq!((mint_m.y.x, mint_m.y.y, mint_m.y.z), (4.0, 5.0, 6.0));
+        assert_eq!(Matrix3d::from(mint_m), m);
+
+        let m = Matrix2i::new(1, 2, 3, 4);
+        assert_eq!(Matrix2i::from(mint::ColumnMatrix2::from(m)), m);
+    }
+
+    #[test]
+    fn test_quaternion_round_trip() {
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), Deg(30.0));
+        let m: mint::Quaternion<f32> = q.into();
+        assert_eq!((m.v.x, m.v.y, m.v.z, m.s), (q.x, q.y, q.z, q.w));
+        assert_eq!(Quaternion::from(m), q);
+    }
+}
@@ -0,0 +1,118 @@
+// Conversions to and from the statically sized nalgebra types
+
+use crate::math::{Matrix, Quaternion, Vector};
+
+impl<T: nalgebra::Scalar, const N: usize> From<nalgebra::SVector<T, N>> for Vector<T, N> {
+    fn from(v: nalgebra::SVector<T, N>) -> Self {
+        Vector(v.into())
+    }
+}
+
+impl<T: nalgebra::Scalar, const N: usize> From<Vector<T, N>> for nalgebra::SVector<T, N> {
+    fn from(v: Vector<T, N>) -> Self {
+        nalgebra::SVector::from(v.0)
+    }
+}
+
+// nalgebra stores columns too, its nested arrays are [[T; R]; C] like ours
+impl<T: nalgebra::Scalar, const R: usize, const C: usize> From<nalgebra::SMatrix<T, R, C>>
+    for Matrix<T, R, C>
+{
+    fn from(m: nalgebra::SMatrix<T, R, C>) -> Self {
+        let columns: [[T; R]; C] = m.into();
+        Matrix(columns.map(Vector))
+    }
+}
+
+impl<T: nalgebra::Scalar, const R: usize, const C: usize> From<Matrix<T, R, C>>
+    for nalgebra::SMatrix<T, R, C>
+{
+    fn from(m: Matrix<T, R, C>) -> Self {
+        nalgebra::SMatrix::from(m.0.map(|column| column.0))
+    }
+}
+
+impl From<nalgebra::Quaternion<f32>> for Quaternion {
+    fn from(q: nalgebra::Quaternion<f32>) -> Self {
+        Quaternion::new(q.i, q.j, q.k, q.w)
+    }
+}
+
+impl From<Quaternion> for nalgebra::Quaternion<f32> {
+    fn from(q: Quaternion) -> Self {
+        nalgebra::Quaternion::new(q.w, q.x, q.y, q.z)
+    }
+}
+
+impl From<nalgebra::UnitQuaternion<f32>> for Quaternion {
+    fn from(q: nalgebra::UnitQuaternion<f32>) -> Self {
+        q.into_inner().into()
+    }
+}
+
+// Renormalizes, so a quaternion that drifted from unit length still makes a valid rotation
+impl From<Quaternion> for nalgebra::UnitQuaternion<f32> {
+    fn from(q: Quaternion) -> Self {
+        nalgebra::UnitQuaternion::new_normalize(q.into())
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod nalgebra_interop_tests {
+    use crate::math::*;
+
+    #[test]
+    fn test_vector_round_trip() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        let n: nalgebra::Vector3<f32> = v.into();
+        assert_eq!(n, nalgebra::Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(Vector3::from(n), v);
+
+        let v = Vector4d::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(Vector4d::from(nalgebra::Vector4::from(v)), v);
+
+        let v = Vector2i::new(-1, 1);
+        assert_eq!(Vector2i::from(nalgebra::Vector2::from(v)), v);
+
+        let v = Vector4u::new(1, 2, 3, 4);
+        assert_eq!(Vector4u::from(nalgebra::Vector4::from(v)), v);
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let m = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0));
+        let n: nalgebra::Matrix4<f32> = m.into();
+        assert_eq!(
+            n,
+            nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(1.0, 2.0, 3.0))
+        );
+        assert_eq!(Matrix4::from(n), m);
+
+        let a = Matrix4::perspective(Deg(60.0), 1.5, 0.1, 100.0);
+        assert!(Matrix4::from(nalgebra::Matrix4::from(a) * n).approx_eq(&(a * m)));
+
+        // Element access agrees, (row, column) on both sides
+        let m = Matrix3d::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        let n = nalgebra::Matrix3::from(m);
+        assert_eq!(n[(0, 1)], 4.0);
+        assert_eq!(Matrix3d::from(n), m);
+
+        let m = Matrix2i::new(1, 2, 3, 4);
+        assert_eq!(Matrix2i::from(nalgebra::Matrix2::from(m)), m);
+    }
+
+    #[test]
+    fn test_quaternion_round_trip() {
+        let q = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), Deg(45.0));
+        let n: nalgebra::Quaternion<f32> = q.into();
+        assert_eq!((n.i, n.j, n.k, n.w), (q.x, q.y, q.z, q.w));
+        assert_eq!(Quaternion::from(n), q);
+
+        let unit: nalgebra::UnitQuaternion<f32> = q.into();
+        let rotated = unit * nalgebra::Vector3::new(0.0, 1.0, 0.0);
+        let expected = q.rotate_vector(Vector3::new(0.0, 1.0, 0.0));
+        assert!((Vector3::from(rotated) - expected).length() < 1e-6);
+        assert!(Quaternion::from(unit).approx_eq(&q));
+    }
+}
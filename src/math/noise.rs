@@ -0,0 +1,834 @@
+// Seeded gradient and cellular noise for terrain and procedural textures.
+// Everything is deterministic for a given seed and uses integer hashing, so results
+// are the same on every platform and don't repeat at any distance.
+use std::f32::consts::TAU;
+
+use crate::math::{Vector2, Vector3, Vector4};
+
+pub trait Noise {
+    fn sample2(&self, point: Vector2) -> f32;
+    fn sample3(&self, point: Vector3) -> f32;
+    fn sample4(&self, point: Vector4) -> f32;
+
+    // 2D noise that repeats every period units along x and y, for seamless textures.
+    // Samples the 4D noise on a torus so features stay about the same size as sample2.
+    fn sample2_tiled(&self, point: Vector2, period: Vector2) -> f32 {
+        let (sin_x, cos_x) = (point.x / period.x * TAU).sin_cos();
+        let (sin_y, cos_y) = (point.y / period.y * TAU).sin_cos();
+        let radius_x = period.x / TAU;
+        let radius_y = period.y / TAU;
+
+        self.sample4(Vector4::new(
+            cos_x * radius_x,
+            sin_x * radius_x,
+            cos_y * radius_y,
+            sin_y * radius_y,
+        ))
+    }
+}
+
+// Classic improved Perlin noise, roughly in [-1, 1] and 0 on integer coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Perlin {
+    seed: u32,
+}
+
+// Smooth gradient noise on the lattices from OpenSimplex2, roughly in [-1, 1].
+// 2D uses the triangular simplex lattice, 3D the rotated body centered cubic lattice
+// with the smooth kernel from OpenSimplex2S, 4D two interleaved hypercubic lattices.
+// Fewer axis aligned artifacts than Perlin. Not bit compatible with the reference code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpenSimplex2 {
+    seed: u32,
+}
+
+// Cellular noise, the distance to the closest of one random feature point per unit cell.
+// sample2/3/4 return that distance (F1), roughly in [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Worley {
+    seed: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FractalKind {
+    // Sum of octaves, in [-1, 1]
+    Fbm,
+    // Sharp ridges where the noise crosses 0, in [0, 1]
+    Ridged,
+    // Sum of absolute octaves, billowy creases, in [0, 1]
+    Turbulence,
+}
+
+// Several octaves of another noise, each at lacunarity times the frequency and gain times
+// the amplitude of the last. Normalized by the total amplitude.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fractal<N> {
+    pub noise: N,
+    pub kind: FractalKind,
+    pub octaves: u32,
+    pub lacunarity: f32,
+    pub gain: f32,
+}
+
+// ###########################  HASHING  ##################################################################
+
+// lowbias32 by Chris Wellons, full avalanche in a few instructions
+fn mix(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    x
+}
+
+fn seed_hash(seed: u64) -> u32 {
+    mix(seed as u32 ^ mix((seed >> 32) as u32 ^ 0x9e37_79b9))
+}
+
+fn hash<const N: usize>(seed: u32, coords: [i32; N]) -> u32 {
+    coords
+        .iter()
+        .fold(seed, |h, c| mix(h.wrapping_add(*c as u32) ^ 0x2545_f491))
+}
+
+// [0, 1) from the top 24 bits
+fn unit_float(h: u32) -> f32 {
+    (h >> 8) as f32 / (1 << 24) as f32
+}
+
+// ###########################  PERLIN  ###################################################################
+
+impl Perlin {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed: seed_hash(seed),
+        }
+    }
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+// Gradients towards the edges and corners of a square
+fn perlin_gradient2(h: u32, x: f32, y: f32) -> f32 {
+    match h & 7 {
+        0 => x + y,
+        1 => -x + y,
+        2 => x - y,
+        3 => -x - y,
+        4 => x,
+        5 => -x,
+        6 => y,
+        _ => -y,
+    }
+}
+
+// Ken Perlin's 12 cube edge gradients, padded to 16
+fn perlin_gradient3(h: u32, x: f32, y: f32, z: f32) -> f32 {
+    let h = h & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+// The 32 gradients with one zero and three +-1 components
+fn perlin_gradient4(h: u32, x: f32, y: f32, z: f32, w: f32) -> f32 {
+    let h = h & 31;
+    let (a, b, c) = match h >> 3 {
+        0 => (y, z, w),
+        1 => (x, z, w),
+        2 => (x, y, w),
+        _ => (x, y, z),
+    };
+
+    (if h & 4 == 0 { -a } else { a })
+        + (if h & 2 == 0 { -b } else { b })
+        + (if h & 1 == 0 { -c } else { c })
+}
+
+impl Noise for Perlin {
+    fn sample2(&self, point: Vector2) -> f32 {
+        let (i, j) = (point.x.floor(), point.y.floor());
+        let (x, y) = (point.x - i, point.y - j);
+        let (i, j) = (i as i32, j as i32);
+        let corner = |di: i32, dj: i32| {
+            let h = hash(self.seed, [i + di, j + dj]);
+            perlin_gradient2(h, x - di as f32, y - dj as f32)
+        };
+
+        let (u, v) = (fade(x), fade(y));
+        lerp(
+            lerp(corner(0, 0), corner(1, 0), u),
+            lerp(corner(0, 1), corner(1, 1), u),
+            v,
+        )
+    }
+
+    fn sample3(&self, point: Vector3) -> f32 {
+        let (i, j, k) = (point.x.floor(), point.y.floor(), point.z.floor());
+        let (x, y, z) = (point.x - i, point.y - j, point.z - k);
+        let (i, j, k) = (i as i32, j as i32, k as i32);
+        let corner = |di: i32, dj: i32, dk: i32| {
+            let h = hash(self.seed, [i + di, j + dj, k + dk]);
+            perlin_gradient3(h, x - di as f32, y - dj as f32, z - dk as f32)
+        };
+
+        let (u, v, s) = (fade(x), fade(y), fade(z));
+        let face = |dk: i32| {
+            lerp(
+                lerp(corner(0, 0, dk), corner(1, 0, dk), u),
+                lerp(corner(0, 1, dk), corner(1, 1, dk), u),
+                v,
+            )
+        };
+
+        lerp(face(0), face(1), s)
+    }
+
+    fn sample4(&self, point: Vector4) -> f32 {
+        let floor = [point.x, point.y, point.z, point.w].map(f32::floor);
+        let local = [
+            point.x - floor[0],
+            point.y - floor[1],
+            point.z - floor[2],
+            point.w - floor[3],
+        ];
+        let cell = floor.map(|f| f as i32);
+        let weights = local.map(fade);
+
+        // Interpolate the 16 corners one axis at a time, corner bit a is the offset along axis a
+        let mut values = [0.0; 16];
+        for (corner, value) in values.iter_mut().enumerate() {
+            let offset = [0, 1, 2, 3].map(|axis| (corner >> axis) as i32 & 1);
+            let h = hash(
+                self.seed,
+                [
+                    cell[0] + offset[0],
+                    cell[1] + offset[1],
+                    cell[2] + offset[2],
+                    cell[3] + offset[3],
+                ],
+            );
+            *value = perlin_gradient4(
+                h,
+                local[0] - offset[0] as f32,
+                local[1] - offset[1] as f32,
+                local[2] - offset[2] as f32,
+                local[3] - offset[3] as f32,
+            );
+        }
+
+        let mut len = 16;
+        for weight in weights {
+            len /= 2;
+            for i in 0..len {
+                values[i] = lerp(values[2 * i], values[2 * i + 1], weight);
+            }
+        }
+
+        values[0] * PERLIN_SCALE_4D
+    }
+}
+
+// 2D and 3D are already in [-1, 1], the 4D gradients are longer
+const PERLIN_SCALE_4D: f32 = 0.85;
+
+// ###########################  OPENSIMPLEX2  #############################################################
+
+impl OpenSimplex2 {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed: seed_hash(seed),
+        }
+    }
+}
+
+const FRAC_1_SQRT_2: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+const GRADIENTS_2D: [[f32; 2]; 8] = [
+    [1.0, 0.0],
+    [-1.0, 0.0],
+    [0.0, 1.0],
+    [0.0, -1.0],
+    [FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+    [-FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+    [FRAC_1_SQRT_2, -FRAC_1_SQRT_2],
+    [-FRAC_1_SQRT_2, -FRAC_1_SQRT_2],
+];
+
+// Cube edge midpoints and corners, normalized
+const GRADIENTS_3D: [[f32; 3]; 16] = {
+    const E: f32 = FRAC_1_SQRT_2;
+    const C: f32 = 0.577_350_26;
+    [
+        [E, E, 0.0],
+        [-E, E, 0.0],
+        [E, -E, 0.0],
+        [-E, -E, 0.0],
+        [E, 0.0, E],
+        [-E, 0.0, E],
+        [E, 0.0, -E],
+        [-E, 0.0, -E],
+        [0.0, E, E],
+        [0.0, -E, E],
+        [0.0, E, -E],
+        [0.0, -E, -E],
+        [C, C, C],
+        [-C, -C, C],
+        [C, -C, -C],
+        [-C, C, -C],
+    ]
+};
+
+// Kernel radius squared. The simplex altitude in 2D, the lattice spacing in 3D and 4D.
+const RADIUS_SQUARED_2D: f32 = 0.5;
+const RADIUS_SQUARED_LATTICE: f32 = 0.75;
+
+fn kernel(radius_squared: f32, distance_squared: f32) -> f32 {
+    let a = radius_squared - distance_squared;
+
+    if a <= 0.0 {
+        return 0.0;
+    }
+
+    let a2 = a * a;
+    a2 * a2
+}
+
+impl OpenSimplex2 {
+    // Corners of the unit (hyper)cube cell around point of the integer lattice shifted by
+    // offset. Covers every lattice point closer than 1, enough for the 0.75 kernel.
+    fn lattice<const N: usize>(&self, point: [f32; N], offset: f32, lattice: i32) -> f32 {
+        let shifted = point.map(|p| p - offset);
+        let cell = shifted.map(|p| p.floor());
+        let mut value = 0.0;
+
+        for corner in 0..1 << N {
+            let mut coords = [0i32; N];
+            let mut delta = [0.0f32; N];
+            let mut distance_squared = 0.0;
+
+            for axis in 0..N {
+                let step = (corner >> axis) & 1;
+                coords[axis] = cell[axis] as i32 + step;
+                delta[axis] = shifted[axis] - cell[axis] - step as f32;
+                distance_squared += delta[axis] * delta[axis];
+            }
+
+            let weight = kernel(RADIUS_SQUARED_LATTICE, distance_squared);
+            if weight == 0.0 {
+                continue;
+            }
+
+            let h = hash(self.seed ^ lattice as u32, coords);
+            value += weight * gradient_dot(h, delta);
+        }
+
+        value
+    }
+}
+
+fn gradient_dot<const N: usize>(h: u32, delta: [f32; N]) -> f32 {
+    match N {
+        3 => {
+            let g = GRADIENTS_3D[(h & 15) as usize];
+            g[0] * delta[0] + g[1] * delta[1] + g[2] * delta[2]
+        }
+        _ => perlin_gradient4(h, delta[0], delta[1], delta[2], delta[3]) * 0.577_350_26,
+    }
+}
+
+impl Noise for OpenSimplex2 {
+    fn sample2(&self, point: Vector2) -> f32 {
+        const SKEW: f32 = 0.366_025_42; // (sqrt(3) - 1) / 2
+        const UNSKEW: f32 = 0.211_324_87; // (3 - sqrt(3)) / 6
+
+        let s = (point.x + point.y) * SKEW;
+        let i = (point.x + s).floor();
+        let j = (point.y + s).floor();
+        let t = (i + j) * UNSKEW;
+        let x0 = point.x - (i - t);
+        let y0 = point.y - (j - t);
+
+        // Lower or upper triangle of the skewed cell
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let (i, j) = (i as i32, j as i32);
+        let corner = |di: i32, dj: i32, x: f32, y: f32| {
+            let weight = kernel(RADIUS_SQUARED_2D, x * x + y * y);
+            if weight == 0.0 {
+                return 0.0;
+            }
+
+            let g = GRADIENTS_2D[(hash(self.seed, [i + di, j + dj]) & 7) as usize];
+            weight * (g[0] * x + g[1] * y)
+        };
+
+        let value = corner(0, 0, x0, y0)
+            + corner(i1, j1, x0 - i1 as f32 + UNSKEW, y0 - j1 as f32 + UNSKEW)
+            + corner(1, 1, x0 - 1.0 + 2.0 * UNSKEW, y0 - 1.0 + 2.0 * UNSKEW);
+
+        value * SIMPLEX_SCALE_2D
+    }
+
+    fn sample3(&self, point: Vector3) -> f32 {
+        // Half turn around the main diagonal, the default orientation of OpenSimplex2
+        let r = (point.x + point.y + point.z) * (2.0 / 3.0);
+        let rotated = [r - point.x, r - point.y, r - point.z];
+
+        (self.lattice(rotated, 0.0, 0) + self.lattice(rotated, 0.5, 1)) * SIMPLEX_SCALE_3D
+    }
+
+    fn sample4(&self, point: Vector4) -> f32 {
+        let point = [point.x, point.y, point.z, point.w];
+
+        (self.lattice(point, 0.0, 0) + self.lattice(point, 0.5, 1)) * SIMPLEX_SCALE_4D
+    }
+}
+
+// Measured over many samples to bring the output close to [-1, 1]
+const SIMPLEX_SCALE_2D: f32 = 96.0;
+const SIMPLEX_SCALE_3D: f32 = 11.5;
+const SIMPLEX_SCALE_4D: f32 = 16.0;
+
+// ###########################  WORLEY  ###################################################################
+
+impl Worley {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed: seed_hash(seed),
+        }
+    }
+
+    // Distances to the closest and second closest feature points (F1, F2).
+    // F2 - F1 gives cell borders. Like most implementations only the neighbouring cells
+    // are searched, which is exact for F1 in practice.
+    pub fn distances2(&self, point: Vector2) -> (f32, f32) {
+        self.distances([point.x, point.y])
+    }
+
+    pub fn distances3(&self, point: Vector3) -> (f32, f32) {
+        self.distances([point.x, point.y, point.z])
+    }
+
+    pub fn distances4(&self, point: Vector4) -> (f32, f32) {
+        self.distances([point.x, point.y, point.z, point.w])
+    }
+
+    fn distances<const N: usize>(&self, point: [f32; N]) -> (f32, f32) {
+        let cell = point.map(|p| p.floor() as i32);
+        let mut closest = f32::MAX;
+        let mut second = f32::MAX;
+
+        // The 3^N cells around and including the point's own
+        for neighbour in 0..3usize.pow(N as u32) {
+            let mut coords = cell;
+            let mut index = neighbour;
+            for coord in coords.iter_mut() {
+                *coord += (index % 3) as i32 - 1;
+                index /= 3;
+            }
+
+            let h = hash(self.seed, coords);
+            let mut distance_squared = 0.0;
+            for axis in 0..N {
+                let feature = coords[axis] as f32 + unit_float(mix(h ^ axis as u32));
+                let delta = feature - point[axis];
+                distance_squared += delta * delta;
+            }
+
+            if distance_squared < closest {
+                second = closest;
+                closest = distance_squared;
+            } else if distance_squared < second {
+                second = distance_squared;
+            }
+        }
+
+        (closest.sqrt(), second.sqrt())
+    }
+}
+
+impl Noise for Worley {
+    fn sample2(&self, point: Vector2) -> f32 {
+        self.distances2(point).0
+    }
+
+    fn sample3(&self, point: Vector3) -> f32 {
+        self.distances3(point).0
+    }
+
+    fn sample4(&self, point: Vector4) -> f32 {
+        self.distances4(point).0
+    }
+}
+
+// ###########################  FRACTAL  ##################################################################
+
+impl<N: Noise> Fractal<N> {
+    // Lacunarity 2 and gain 0.5, the usual defaults
+    pub fn new(noise: N, kind: FractalKind, octaves: u32) -> Self {
+        Self {
+            noise,
+            kind,
+            octaves,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    pub fn fbm(noise: N, octaves: u32) -> Self {
+        Self::new(noise, FractalKind::Fbm, octaves)
+    }
+
+    pub fn ridged(noise: N, octaves: u32) -> Self {
+        Self::new(noise, FractalKind::Ridged, octaves)
+    }
+
+    pub fn turbulence(noise: N, octaves: u32) -> Self {
+        Self::new(noise, FractalKind::Turbulence, octaves)
+    }
+
+    // @params: sample gets the frequency and an offset that decorrelates the octaves
+    fn accumulate(&self, mut sample: impl FnMut(f32, f32) -> f32) -> f32 {
+        let mut frequency = 1.0;
+        let mut amplitude = 1.0;
+        let mut sum = 0.0;
+        let mut total = 0.0;
+
+        for octave in 0..self.octaves {
+            let value = sample(frequency, octave as f32 * OCTAVE_OFFSET);
+
+            sum += amplitude
+                * match self.kind {
+                    FractalKind::Fbm => value,
+                    FractalKind::Ridged => (1.0 - value.abs()) * (1.0 - value.abs()),
+                    FractalKind::Turbulence => value.abs(),
+                };
+            total += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        sum / total
+    }
+}
+
+// Irrational so octaves never line up on the lattice again
+const OCTAVE_OFFSET: f32 = 17.319_508;
+
+impl<N: Noise> Noise for Fractal<N> {
+    fn sample2(&self, point: Vector2) -> f32 {
+        self.accumulate(|frequency, offset| {
+            self.noise
+                .sample2(point * frequency + Vector2::splat(offset))
+        })
+    }
+
+    fn sample3(&self, point: Vector3) -> f32 {
+        self.accumulate(|frequency, offset| {
+            self.noise
+                .sample3(point * frequency + Vector3::splat(offset))
+        })
+    }
+
+    fn sample4(&self, point: Vector4) -> f32 {
+        self.accumulate(|frequency, offset| {
+            self.noise
+                .sample4(point * frequency + Vector4::splat(offset))
+        })
+    }
+}
+
+// ###########################  IMAGES  ###################################################################
+
+// Fills a single channel image for texture::tex_image_2d with InternalFormat::RED.
+// Rows go from the bottom up like glTexImage2D expects. sample gets the texture coordinates
+// of each pixel center in [0, 1], and its result is mapped from [-1, 1] to [0, 255] and clamped.
+// Panics if the buffer isn't width * height bytes.
+pub fn fill_image_u8(
+    buffer: &mut [u8],
+    width: usize,
+    height: usize,
+    mut sample: impl FnMut(Vector2) -> f32,
+) {
+    fill_image(buffer, width, height, |uv| {
+        ((sample(uv) * 0.5 + 0.5).clamp(0.0, 1.0) * 255.0).round() as u8
+    });
+}
+
+// Same layout as fill_image_u8 with the values stored as is, for float textures or
+// heightmaps on the CPU
+pub fn fill_image_f32(
+    buffer: &mut [f32],
+    width: usize,
+    height: usize,
+    sample: impl FnMut(Vector2) -> f32,
+) {
+    fill_image(buffer, width, height, sample);
+}
+
+fn fill_image<T>(
+    buffer: &mut [T],
+    width: usize,
+    height: usize,
+    mut sample: impl FnMut(Vector2) -> T,
+) {
+    if buffer.len() != width * height {
+        panic!("Buffer provided to fill_image doesn't match width * height!");
+    }
+
+    for (i, pixel) in buffer.iter_mut().enumerate() {
+        let uv = Vector2::new(
+            ((i % width) as f32 + 0.5) / width as f32,
+            ((i / width) as f32 + 0.5) / height as f32,
+        );
+        *pixel = sample(uv);
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod noise_tests {
+    use crate::math::noise::{
+        fill_image_f32, fill_image_u8, Fractal, Noise, OpenSimplex2, Perlin, Worley,
+    };
+    use crate::math::*;
+
+    // Deterministic spread of sample points without depending on a random number generator
+    fn sample_points(count: usize) -> impl Iterator<Item = Vector4> {
+        (0..count).map(|i| {
+            let i = i as f32;
+            Vector4::new(
+                (i * 0.618_034).fract() * 40.0 - 20.0,
+                (i * 0.754_878).fract() * 40.0 - 20.0,
+                (i * 0.569_840).fract() * 40.0 - 20.0,
+                (i * 0.362_369).fract() * 40.0 - 20.0,
+            )
+        })
+    }
+
+    fn gradient_noises() -> [(&'static str, Box<dyn Noise>); 2] {
+        [
+            ("Perlin", Box::new(Perlin::new(7))),
+            ("OpenSimplex2", Box::new(OpenSimplex2::new(7))),
+        ]
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let point = Vector4::new(1.3, -2.7, 0.4, 5.1);
+
+        assert_eq!(
+            Perlin::new(3).sample3(point.xyz()),
+            Perlin::new(3).sample3(point.xyz())
+        );
+        assert_ne!(
+            Perlin::new(3).sample3(point.xyz()),
+            Perlin::new(4).sample3(point.xyz())
+        );
+        assert_eq!(
+            OpenSimplex2::new(3).sample4(point),
+            OpenSimplex2::new(3).sample4(point)
+        );
+        assert_ne!(
+            OpenSimplex2::new(3).sample4(point),
+            OpenSimplex2::new(4).sample4(point)
+        );
+        assert_eq!(
+            Worley::new(3).sample2(point.xy()),
+            Worley::new(3).sample2(point.xy())
+        );
+        assert_ne!(
+            Worley::new(3).sample2(point.xy()),
+            Worley::new(4).sample2(point.xy())
+        );
+
+        // Seeds that only differ in the high bits still differ
+        assert_ne!(
+            Perlin::new(1 << 40).sample2(point.xy()),
+            Perlin::new(2 << 40).sample2(point.xy())
+        );
+    }
+
+    #[test]
+    fn test_perlin_zero_on_lattice() {
+        let perlin = Perlin::new(11);
+
+        for i in -3..3 {
+            let p = Vector4::new(i as f32, (i * 2) as f32, -i as f32, 7.0);
+            assert_eq!(perlin.sample2(p.xy()), 0.0);
+            assert_eq!(perlin.sample3(p.xyz()), 0.0);
+            assert_eq!(perlin.sample4(p), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_gradient_noise_range() {
+        for (name, noise) in gradient_noises() {
+            let mut max = [0.0f32; 3];
+
+            for p in sample_points(5000) {
+                let values = [
+                    noise.sample2(p.xy()),
+                    noise.sample3(p.xyz()),
+                    noise.sample4(p),
+                ];
+                for (max, value) in max.iter_mut().zip(values) {
+                    assert!(value.abs() <= 1.0, "{} out of range: {}", name, value);
+                    *max = max.max(value.abs());
+                }
+            }
+
+            // Uses most of the range in every dimension
+            assert!(max.iter().all(|m| *m > 0.6), "{} {:?}", name, max);
+        }
+    }
+
+    #[test]
+    fn test_continuity() {
+        // Small steps give small changes, catches kernels cut off before reaching zero
+        let step = Vector4::new(1e-3, 7e-4, 5e-4, 3e-4);
+
+        for (name, noise) in gradient_noises() {
+            let mut point = Vector4::new(-2.0, 1.0, 0.5, -1.0);
+
+            for _ in 0..8000 {
+                let next = point + step;
+                assert!(
+                    (noise.sample2(point.xy()) - noise.sample2(next.xy())).abs() < 0.02,
+                    "{}",
+                    name
+                );
+                assert!(
+                    (noise.sample3(point.xyz()) - noise.sample3(next.xyz())).abs() < 0.02,
+                    "{}",
+                    name
+                );
+                assert!(
+                    (noise.sample4(point) - noise.sample4(next)).abs() < 0.02,
+                    "{}",
+                    name
+                );
+                point = next;
+            }
+        }
+    }
+
+    #[test]
+    fn test_worley() {
+        let worley = Worley::new(5);
+
+        for p in sample_points(2000) {
+            for (f1, f2) in [
+                worley.distances2(p.xy()),
+                worley.distances3(p.xyz()),
+                worley.distances4(p),
+            ] {
+                assert!(f1 >= 0.0 && f1 <= f2);
+                assert!(f1 < 1.5);
+            }
+        }
+
+        // Walking through a cell passes close to its feature point somewhere
+        let closest = (0..10000)
+            .map(|i| {
+                let p = Vector2::new((i % 100) as f32 / 100.0, (i / 100) as f32 / 100.0);
+                worley.sample2(p)
+            })
+            .fold(f32::MAX, f32::min);
+        assert!(closest < 0.01);
+    }
+
+    #[test]
+    fn test_tiled() {
+        let period = Vector2::new(4.0, 6.0);
+        let noises: [Box<dyn Noise>; 3] = [
+            Box::new(Perlin::new(2)),
+            Box::new(Worley::new(2)),
+            Box::new(Fractal::fbm(OpenSimplex2::new(2), 4)),
+        ];
+
+        for noise in noises {
+            for p in sample_points(200) {
+                let value = noise.sample2_tiled(p.xy(), period);
+                let shifted_x = noise.sample2_tiled(p.xy() + Vector2::new(4.0, 0.0), period);
+                let shifted_y = noise.sample2_tiled(p.xy() - Vector2::new(0.0, 12.0), period);
+
+                assert!((value - shifted_x).abs() < 1e-3);
+                assert!((value - shifted_y).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fractal() {
+        let simplex = OpenSimplex2::new(9);
+
+        // One octave is the noise itself
+        let single = Fractal::fbm(simplex, 1);
+        let point = Vector3::new(0.3, 1.7, -2.2);
+        assert_eq!(single.sample3(point), simplex.sample3(point));
+        assert_eq!(Fractal::fbm(simplex, 0).sample3(point), 0.0);
+
+        let fbm = Fractal::fbm(simplex, 5);
+        let ridged = Fractal::ridged(simplex, 5);
+        let turbulence = Fractal::turbulence(simplex, 5);
+        for p in sample_points(2000) {
+            assert!(fbm.sample2(p.xy()).abs() <= 1.0);
+            assert!((0.0..=1.0).contains(&ridged.sample3(p.xyz())));
+            assert!((0.0..=1.0).contains(&turbulence.sample4(p)));
+        }
+
+        // Higher octaves add detail, so fbm differs from the base noise
+        assert_ne!(fbm.sample3(point), simplex.sample3(point));
+    }
+
+    #[test]
+    fn test_fill_image() {
+        let mut bytes = vec![0u8; 4 * 2];
+        fill_image_u8(&mut bytes, 4, 2, |uv| uv.x * 2.0 - 1.0);
+        assert_eq!(bytes, vec![32, 96, 159, 223, 32, 96, 159, 223]);
+
+        // Out of range values are clamped
+        fill_image_u8(&mut bytes, 4, 2, |uv| (uv.y - 0.5) * 10.0);
+        assert_eq!(&bytes[..4], &[0; 4]);
+        assert_eq!(&bytes[4..], &[255; 4]);
+
+        let worley = Worley::new(1);
+        let mut floats = vec![0.0f32; 8 * 8];
+        fill_image_f32(&mut floats, 8, 8, |uv| worley.sample2(uv * 4.0));
+        assert_eq!(
+            floats[8 * 3 + 5],
+            worley.sample2(Vector2::new(5.5, 3.5) / 8.0 * 4.0)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fill_image_size_mismatch() {
+        fill_image_u8(&mut [0; 10], 4, 3, |_| 0.0);
+    }
+}
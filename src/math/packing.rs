@@ -0,0 +1,508 @@
+// Compact vertex attribute formats. Packed values go straight into the byte buffers for
+// buffer::Buffer::buffer_data through as_bytes, and match the GL types noted on each function.
+use crate::math::{Vector2, Vector3, Vector4};
+
+// ###########################  HALF FLOATS  ##############################################################
+
+// IEEE 754 binary16 bits (GL_HALF_FLOAT), rounded to nearest even.
+// Overflows to infinity past 65504, NaN stays NaN.
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x007f_ffff;
+
+    // Infinity or NaN, NaN keeps a non-zero quiet mantissa
+    if exponent == 0xff {
+        let nan = if mantissa != 0 {
+            0x0200 | (mantissa >> 13) as u16
+        } else {
+            0
+        };
+        return sign | 0x7c00 | nan;
+    }
+
+    let half_exponent = exponent - 127 + 15;
+
+    if half_exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+
+    if half_exponent <= 0 {
+        // Below half of the smallest subnormal, rounds to zero
+        if half_exponent < -10 {
+            return sign;
+        }
+
+        // Subnormal, shift the mantissa with its implicit 1 down to units of 2^-24
+        let full = mantissa | 0x0080_0000;
+        let shift = (14 - half_exponent) as u32;
+        return sign | round_shift(full, shift) as u16;
+    }
+
+    // A carry out of the mantissa bumps the exponent, up to infinity
+    let half = ((half_exponent as u32) << 10) + round_shift(mantissa, 13);
+    sign | half as u16
+}
+
+// Exact, every half float is representable as an f32
+pub fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exponent = ((half >> 10) & 0x1f) as u32;
+    let mantissa = (half & 0x03ff) as u32;
+
+    let bits = match exponent {
+        0 => {
+            // Zero or subnormal, mantissa * 2^-24
+            let value = mantissa as f32 * (1.0 / (1 << 24) as f32);
+            return f32::from_bits(sign | value.to_bits());
+        }
+        0x1f => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+
+    f32::from_bits(bits)
+}
+
+// value >> shift, rounded to nearest even
+fn round_shift(value: u32, shift: u32) -> u32 {
+    let shifted = value >> shift;
+    let remainder = value & ((1 << shift) - 1);
+    let halfway = 1 << (shift - 1);
+
+    if remainder > halfway || (remainder == halfway && shifted & 1 == 1) {
+        shifted + 1
+    } else {
+        shifted
+    }
+}
+
+pub fn pack_half2(v: Vector2) -> [u16; 2] {
+    v.as_array().map(f32_to_f16)
+}
+
+pub fn pack_half3(v: Vector3) -> [u16; 3] {
+    v.as_array().map(f32_to_f16)
+}
+
+pub fn pack_half4(v: Vector4) -> [u16; 4] {
+    v.as_array().map(f32_to_f16)
+}
+
+pub fn unpack_half2(packed: [u16; 2]) -> Vector2 {
+    Vector2::from_array(packed.map(f16_to_f32))
+}
+
+pub fn unpack_half3(packed: [u16; 3]) -> Vector3 {
+    Vector3::from_array(packed.map(f16_to_f32))
+}
+
+pub fn unpack_half4(packed: [u16; 4]) -> Vector4 {
+    Vector4::from_array(packed.map(f16_to_f32))
+}
+
+// ###########################  NORMALIZED INTEGERS  ######################################################
+// Same conversions as GL with normalized set to GL_TRUE. Inputs are clamped to [0, 1] for
+// unorm and [-1, 1] for snorm, NaN packs to 0. The round trip error is at most half a step.
+
+// GL_UNSIGNED_BYTE
+pub fn pack_unorm8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+pub fn unpack_unorm8(packed: u8) -> f32 {
+    packed as f32 / 255.0
+}
+
+// GL_UNSIGNED_SHORT
+pub fn pack_unorm16(value: f32) -> u16 {
+    (value.clamp(0.0, 1.0) * 65535.0).round() as u16
+}
+
+pub fn unpack_unorm16(packed: u16) -> f32 {
+    packed as f32 / 65535.0
+}
+
+// GL_BYTE, -128 and -127 both unpack to -1
+pub fn pack_snorm8(value: f32) -> i8 {
+    (value.clamp(-1.0, 1.0) * 127.0).round() as i8
+}
+
+pub fn unpack_snorm8(packed: i8) -> f32 {
+    (packed as f32 / 127.0).max(-1.0)
+}
+
+// GL_SHORT
+pub fn pack_snorm16(value: f32) -> i16 {
+    (value.clamp(-1.0, 1.0) * 32767.0).round() as i16
+}
+
+pub fn unpack_snorm16(packed: i16) -> f32 {
+    (packed as f32 / 32767.0).max(-1.0)
+}
+
+// ###########################  10_10_10_2  ###############################################################
+// 10 bits each for x, y and z and 2 for w in one u32, x in the lowest bits.
+// GL_UNSIGNED_INT_2_10_10_10_REV and GL_INT_2_10_10_10_REV with a size of 4 (or GL_BGRA).
+
+pub fn pack_unorm_10_10_10_2(v: Vector4) -> u32 {
+    let x = (v.x.clamp(0.0, 1.0) * 1023.0).round() as u32;
+    let y = (v.y.clamp(0.0, 1.0) * 1023.0).round() as u32;
+    let z = (v.z.clamp(0.0, 1.0) * 1023.0).round() as u32;
+    let w = (v.w.clamp(0.0, 1.0) * 3.0).round() as u32;
+
+    x | (y << 10) | (z << 20) | (w << 30)
+}
+
+pub fn unpack_unorm_10_10_10_2(packed: u32) -> Vector4 {
+    Vector4::new(
+        (packed & 0x3ff) as f32 / 1023.0,
+        ((packed >> 10) & 0x3ff) as f32 / 1023.0,
+        ((packed >> 20) & 0x3ff) as f32 / 1023.0,
+        (packed >> 30) as f32 / 3.0,
+    )
+}
+
+// Normals and tangents, w holds the tangent handedness as -1 or 1
+pub fn pack_snorm_10_10_10_2(v: Vector4) -> u32 {
+    let x = (v.x.clamp(-1.0, 1.0) * 511.0).round() as i32 as u32 & 0x3ff;
+    let y = (v.y.clamp(-1.0, 1.0) * 511.0).round() as i32 as u32 & 0x3ff;
+    let z = (v.z.clamp(-1.0, 1.0) * 511.0).round() as i32 as u32 & 0x3ff;
+    let w = v.w.clamp(-1.0, 1.0).round() as i32 as u32 & 0x3;
+
+    x | (y << 10) | (z << 20) | (w << 30)
+}
+
+pub fn unpack_snorm_10_10_10_2(packed: u32) -> Vector4 {
+    // Shift each field to the top and back down to sign extend it
+    let field = |shift: u32| ((packed << (22 - shift)) as i32 >> 22) as f32;
+
+    Vector4::new(
+        (field(0) / 511.0).max(-1.0),
+        (field(10) / 511.0).max(-1.0),
+        (field(20) / 511.0).max(-1.0),
+        ((packed as i32 >> 30) as f32).max(-1.0),
+    )
+}
+
+// ###########################  OCTAHEDRAL NORMALS  ######################################################
+// Unit vectors folded onto an octahedron and flattened into [-1, 1]^2 (Cigolle et al. 2014).
+// Two components with a near uniform error over the sphere, unlike storing x and y.
+
+// @params: normal must be normalized
+pub fn encode_octahedral(normal: Vector3) -> Vector2 {
+    let l1 = normal.x.abs() + normal.y.abs() + normal.z.abs();
+    let p = Vector2::new(normal.x / l1, normal.y / l1);
+
+    if normal.z >= 0.0 {
+        return p;
+    }
+
+    // Fold the lower half over the diagonals
+    Vector2::new(
+        (1.0 - p.y.abs()) * sign_not_zero(p.x),
+        (1.0 - p.x.abs()) * sign_not_zero(p.y),
+    )
+}
+
+pub fn decode_octahedral(encoded: Vector2) -> Vector3 {
+    let z = 1.0 - encoded.x.abs() - encoded.y.abs();
+    let fold = (-z).max(0.0);
+
+    Vector3::new(
+        encoded.x - fold * sign_not_zero(encoded.x),
+        encoded.y - fold * sign_not_zero(encoded.y),
+        z,
+    )
+    .normalized()
+}
+
+fn sign_not_zero(value: f32) -> f32 {
+    if value >= 0.0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+// 2 x GL_SHORT normalized, under 0.005 degrees of error
+pub fn pack_octahedral16(normal: Vector3) -> [i16; 2] {
+    encode_octahedral(normal).as_array().map(pack_snorm16)
+}
+
+pub fn unpack_octahedral16(packed: [i16; 2]) -> Vector3 {
+    decode_octahedral(Vector2::from_array(packed.map(unpack_snorm16)))
+}
+
+// 2 x GL_BYTE normalized, under 1.5 degrees of error
+pub fn pack_octahedral8(normal: Vector3) -> [i8; 2] {
+    encode_octahedral(normal).as_array().map(pack_snorm8)
+}
+
+pub fn unpack_octahedral8(packed: [i8; 2]) -> Vector3 {
+    decode_octahedral(Vector2::from_array(packed.map(unpack_snorm8)))
+}
+
+// ###########################  BYTES  ####################################################################
+// Integer types the packing functions produce, sealed so as_bytes only sees types without padding
+pub trait Packed: sealed::Sealed + Copy {}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_packed {
+    ($($t:ty),*) => {$(
+        impl sealed::Sealed for $t {}
+        impl Packed for $t {}
+    )*};
+}
+
+impl_packed!(u8, i8, u16, i16, u32, i32);
+
+// Packed data as bytes in native order, e.g. a slice of [u16; 4] flattened with as_flattened
+pub fn as_bytes<T: Packed>(values: &[T]) -> &[u8] {
+    // SAFETY: Packed is only implemented for integers, which have no padding, and u8 has no
+    // alignment requirement
+    unsafe {
+        std::slice::from_raw_parts(values.as_ptr() as *const u8, std::mem::size_of_val(values))
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod packing_tests {
+    use crate::math::packing::{
+        as_bytes, decode_octahedral, encode_octahedral, f16_to_f32, f32_to_f16, pack_half3,
+        pack_octahedral16, pack_octahedral8, pack_snorm16, pack_snorm8, pack_snorm_10_10_10_2,
+        pack_unorm16, pack_unorm8, pack_unorm_10_10_10_2, unpack_half3, unpack_octahedral16,
+        unpack_octahedral8, unpack_snorm16, unpack_snorm8, unpack_snorm_10_10_10_2, unpack_unorm16,
+        unpack_unorm8, unpack_unorm_10_10_10_2,
+    };
+    use crate::math::sampling::{sample_sphere, sobol2};
+    use crate::math::*;
+
+    #[test]
+    fn test_half_known_values() {
+        assert_eq!(f32_to_f16(0.0), 0x0000);
+        assert_eq!(f32_to_f16(-0.0), 0x8000);
+        assert_eq!(f32_to_f16(1.0), 0x3c00);
+        assert_eq!(f32_to_f16(-2.0), 0xc000);
+        assert_eq!(f32_to_f16(0.5), 0x3800);
+        assert_eq!(f32_to_f16(65504.0), 0x7bff);
+        assert_eq!(f32_to_f16(f32::INFINITY), 0x7c00);
+        assert_eq!(f32_to_f16(f32::NEG_INFINITY), 0xfc00);
+        assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+
+        // Overflow, the largest value that still rounds down is 65519
+        assert_eq!(f32_to_f16(65519.0), 0x7bff);
+        assert_eq!(f32_to_f16(65520.0), 0x7c00);
+        assert_eq!(f32_to_f16(1e10), 0x7c00);
+
+        // Subnormals and underflow
+        assert_eq!(f32_to_f16(2.0f32.powi(-24)), 0x0001);
+        assert_eq!(f32_to_f16(2.0f32.powi(-14)), 0x0400);
+        assert_eq!(f32_to_f16(2.0f32.powi(-25)), 0x0000);
+        assert_eq!(f32_to_f16(2.0f32.powi(-25) * 1.5), 0x0001);
+        assert_eq!(f32_to_f16(1e-10), 0x0000);
+        assert_eq!(f16_to_f32(0x0001), 2.0f32.powi(-24));
+        assert_eq!(f16_to_f32(0x83ff), -1023.0 * 2.0f32.powi(-24));
+    }
+
+    #[test]
+    fn test_half_round_to_nearest_even() {
+        // 1 + 2^-11 is halfway between 1 and the next half, ties go to the even 1
+        assert_eq!(f32_to_f16(1.0 + 2.0f32.powi(-11)), 0x3c00);
+        // 1 + 3 * 2^-11 is halfway between odd 0x3c01 and even 0x3c02
+        assert_eq!(f32_to_f16(1.0 + 3.0 * 2.0f32.powi(-11)), 0x3c02);
+        assert_eq!(
+            f32_to_f16(1.0 + 2.0f32.powi(-11) + 2.0f32.powi(-20)),
+            0x3c01
+        );
+        // Rounding up carries into the exponent
+        assert_eq!(f32_to_f16(2.0 - 2.0f32.powi(-12)), 0x4000);
+    }
+
+    #[test]
+    fn test_half_round_trip_every_value() {
+        for half in 0..=u16::MAX {
+            let value = f16_to_f32(half);
+
+            if value.is_nan() {
+                assert!(f16_to_f32(f32_to_f16(value)).is_nan());
+            } else {
+                assert_eq!(f32_to_f16(value), half, "{:#06x}", half);
+            }
+        }
+    }
+
+    #[test]
+    fn test_half_error_bound() {
+        // Relative error of normal halves is at most 2^-11, half a unit in the last place
+        for i in 0..10000 {
+            let value = (i as f32 * 0.618_034).fract() * 60000.0 + 6.2e-5;
+            let round_trip = f16_to_f32(f32_to_f16(value));
+            assert!(
+                (round_trip - value).abs() <= value * 2.0f32.powi(-11),
+                "{}",
+                value
+            );
+        }
+
+        let v = Vector3::new(1.5, -0.25, 1000.0);
+        assert_eq!(unpack_half3(pack_half3(v)), v);
+    }
+
+    #[test]
+    fn test_normalized_integers() {
+        assert_eq!(pack_unorm8(1.0), 255);
+        assert_eq!(pack_unorm8(2.0), 255);
+        assert_eq!(pack_unorm8(-1.0), 0);
+        assert_eq!(pack_unorm8(f32::NAN), 0);
+        assert_eq!(pack_unorm8(0.5), 128);
+        assert_eq!(pack_unorm16(1.0), 65535);
+        assert_eq!(pack_snorm8(-1.0), -127);
+        assert_eq!(pack_snorm8(1.0), 127);
+        assert_eq!(pack_snorm16(-2.0), -32767);
+        assert_eq!(unpack_snorm8(-128), -1.0);
+        assert_eq!(unpack_snorm8(0), 0.0);
+        assert_eq!(unpack_snorm16(i16::MIN), -1.0);
+
+        // Round trip error is at most half a step
+        for i in 0..=1000 {
+            let unit = i as f32 / 1000.0;
+            let signed = unit * 2.0 - 1.0;
+
+            assert!((unpack_unorm8(pack_unorm8(unit)) - unit).abs() <= 0.5 / 255.0 + 1e-7);
+            assert!((unpack_unorm16(pack_unorm16(unit)) - unit).abs() <= 0.5 / 65535.0 + 1e-7);
+            assert!((unpack_snorm8(pack_snorm8(signed)) - signed).abs() <= 0.5 / 127.0 + 1e-7);
+            assert!((unpack_snorm16(pack_snorm16(signed)) - signed).abs() <= 0.5 / 32767.0 + 1e-7);
+        }
+
+        // Every packed value survives a round trip
+        for packed in 0..=255u8 {
+            assert_eq!(pack_unorm8(unpack_unorm8(packed)), packed);
+        }
+        for packed in -127..=127i8 {
+            assert_eq!(pack_snorm8(unpack_snorm8(packed)), packed);
+        }
+    }
+
+    #[test]
+    fn test_10_10_10_2() {
+        assert_eq!(
+            pack_unorm_10_10_10_2(Vector4::new(1.0, 0.0, 0.0, 0.0)),
+            0x3ff
+        );
+        assert_eq!(
+            pack_unorm_10_10_10_2(Vector4::new(0.0, 0.0, 0.0, 1.0)),
+            0xc000_0000
+        );
+        assert_eq!(pack_unorm_10_10_10_2(Vector4::splat(1.0)), u32::MAX);
+        assert_eq!(
+            pack_snorm_10_10_10_2(Vector4::new(-1.0, 0.0, 0.0, 0.0)),
+            0x201
+        );
+        assert_eq!(
+            pack_snorm_10_10_10_2(Vector4::new(0.0, 0.0, 0.0, -1.0)),
+            0xc000_0000
+        );
+
+        for i in 0..=100 {
+            let t = i as f32 / 100.0;
+            let unsigned = Vector4::new(t, 1.0 - t, t * t, (t * 3.0).round() / 3.0);
+            let signed = Vector4::new(t * 2.0 - 1.0, -t, t * 0.5, if t < 0.5 { -1.0 } else { 1.0 });
+
+            let round_trip = unpack_unorm_10_10_10_2(pack_unorm_10_10_10_2(unsigned));
+            assert!((round_trip.xyz() - unsigned.xyz())
+                .as_array()
+                .iter()
+                .all(|e| e.abs() <= 0.5 / 1023.0 + 1e-7));
+            assert_eq!(round_trip.w, unsigned.w);
+
+            let round_trip = unpack_snorm_10_10_10_2(pack_snorm_10_10_10_2(signed));
+            assert!((round_trip.xyz() - signed.xyz())
+                .as_array()
+                .iter()
+                .all(|e| e.abs() <= 0.5 / 511.0 + 1e-7));
+            assert_eq!(round_trip.w, signed.w);
+        }
+
+        // The most negative field value clamps to -1
+        assert_eq!(unpack_snorm_10_10_10_2(0x200).x, -1.0);
+    }
+
+    #[test]
+    fn test_octahedral() {
+        // Axes land on the corners and edge midpoints of the square
+        assert_eq!(
+            encode_octahedral(Vector3::new(0.0, 0.0, 1.0)),
+            Vector2::new(0.0, 0.0)
+        );
+        assert_eq!(
+            encode_octahedral(Vector3::new(1.0, 0.0, 0.0)),
+            Vector2::new(1.0, 0.0)
+        );
+        assert_eq!(encode_octahedral(Vector3::new(0.0, 0.0, -1.0)).x.abs(), 1.0);
+
+        let mut worst16 = 0.0f32;
+        let mut worst8 = 0.0f32;
+
+        for i in 0..20000 {
+            let normal = sample_sphere(sobol2(i));
+
+            let encoded = encode_octahedral(normal);
+            assert!(encoded.x.abs() <= 1.0 && encoded.y.abs() <= 1.0);
+            assert!((decode_octahedral(encoded) - normal).length() < 1e-5);
+
+            // atan2 stays accurate for tiny angles where acos of the dot product doesn't
+            let angle =
+                |decoded: Vector3| decoded.cross(normal).length().atan2(decoded.dot(normal));
+            worst16 = worst16.max(angle(unpack_octahedral16(pack_octahedral16(normal))));
+            worst8 = worst8.max(angle(unpack_octahedral8(pack_octahedral8(normal))));
+        }
+
+        assert!(
+            convert_to_degrees(worst16) < 0.005,
+            "{}",
+            convert_to_degrees(worst16)
+        );
+        assert!(
+            convert_to_degrees(worst8) < 1.5,
+            "{}",
+            convert_to_degrees(worst8)
+        );
+    }
+
+    #[test]
+    fn test_interleaved_vertex() {
+        // Position as 3 halves plus padding, normal as 10_10_10_2: 12 bytes instead of 24
+        let position = Vector3::new(1.0, 2.5, -3.0);
+        let normal = Vector3::new(0.0, 1.0, 0.0);
+        let [x, y, z] = pack_half3(position);
+        let mut bytes = as_bytes(&[x, y, z, 0]).to_vec();
+        bytes.extend_from_slice(as_bytes(&[pack_snorm_10_10_10_2(normal.extend(1.0))]));
+
+        assert_eq!(bytes.len(), 12);
+        let packed_normal = u32::from_ne_bytes(bytes[8..12].try_into().unwrap());
+        assert_eq!(unpack_snorm_10_10_10_2(packed_normal), normal.extend(1.0));
+    }
+
+    #[test]
+    fn test_as_bytes() {
+        let halves = [f32_to_f16(1.0), f32_to_f16(-2.0)];
+        let bytes = as_bytes(&halves);
+        assert_eq!(bytes.len(), 4);
+        assert_eq!(bytes[0..2], halves[0].to_ne_bytes());
+        assert_eq!(bytes[2..4], halves[1].to_ne_bytes());
+
+        let octahedral = [pack_octahedral16(Vector3::new(0.0, 0.0, 1.0)); 3];
+        let bytes = as_bytes(octahedral.as_flattened());
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(bytes[4..6], octahedral[1][0].to_ne_bytes());
+
+        let packed = pack_unorm_10_10_10_2(Vector4::new(1.0, 0.0, 0.5, 1.0));
+        assert_eq!(as_bytes(&[packed]), packed.to_ne_bytes());
+        assert!(as_bytes::<u32>(&[]).is_empty());
+    }
+}
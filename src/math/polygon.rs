@@ -0,0 +1,855 @@
+// Polygon queries, triangulation and convex hulls for building meshes from outlines and
+// point clouds. A polygon is a slice of points in order, the last one connects back to the
+// first. Triangles come out as u32 indices into the input, three per triangle and counter
+// clockwise like GL's default front face, ready for a BufferType::ElementArray buffer drawn
+// with GL_UNSIGNED_INT (see packing::as_bytes).
+// Everything is computed in f64 internally, so thin slivers don't flip orientation.
+use std::collections::HashSet;
+
+use crate::math::{Vector2, Vector2d, Vector3, Vector3d};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Winding {
+    CounterClockwise,
+    Clockwise,
+}
+
+// Positive for counter clockwise polygons (shoelace formula)
+pub fn signed_area(polygon: &[Vector2]) -> f32 {
+    (twice_signed_area(polygon.iter().map(|p| p.convert())) * 0.5) as f32
+}
+
+pub fn area(polygon: &[Vector2]) -> f32 {
+    signed_area(polygon).abs()
+}
+
+// None for degenerate polygons with no area
+pub fn winding(polygon: &[Vector2]) -> Option<Winding> {
+    let area = twice_signed_area(polygon.iter().map(|p| p.convert()));
+
+    if area > 0.0 {
+        Some(Winding::CounterClockwise)
+    } else if area < 0.0 {
+        Some(Winding::Clockwise)
+    } else {
+        None
+    }
+}
+
+// Even-odd rule, so it works for either winding and self intersecting outlines.
+// Points exactly on an edge can land on either side.
+pub fn contains_point(polygon: &[Vector2], point: Vector2) -> bool {
+    let mut inside = false;
+
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+
+        if (a.y > point.y) != (b.y > point.y) {
+            let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+// ###########################  TRIANGULATION  ############################################################
+
+// Ear clipping, the polygon can wind either way. Collinear points are allowed but don't get
+// triangles of their own. None with fewer than 3 points or when it runs out of ears, which
+// only happens for outlines that cross themselves. Those can also give partial results.
+pub fn triangulate(polygon: &[Vector2]) -> Option<Vec<u32>> {
+    triangulate_with_holes(polygon, &[])
+}
+
+// Like triangulate, with holes cut out of the outline. Holes can wind either way but must be
+// inside the outline and not touch each other. Indices count the outline first and then
+// each hole in order, as if they were all one array.
+pub fn triangulate_with_holes(outline: &[Vector2], holes: &[&[Vector2]]) -> Option<Vec<u32>> {
+    if outline.len() < 3 || holes.iter().any(|hole| hole.len() < 3) {
+        return None;
+    }
+
+    let points: Vec<Vector2d> = outline
+        .iter()
+        .chain(holes.iter().flat_map(|hole| hole.iter()))
+        .map(|p| p.convert())
+        .collect();
+
+    // The outline goes counter clockwise and the holes clockwise, so the inside is always
+    // on the left once the holes are joined in
+    let mut ring: Vec<usize> = (0..outline.len()).collect();
+    if ring_area(&points, &ring) < 0.0 {
+        ring.reverse();
+    }
+
+    let mut hole_rings = Vec::with_capacity(holes.len());
+    let mut start = outline.len();
+    for hole in holes {
+        let mut hole_ring: Vec<usize> = (start..start + hole.len()).collect();
+        if ring_area(&points, &hole_ring) > 0.0 {
+            hole_ring.reverse();
+        }
+
+        start += hole.len();
+        hole_rings.push(hole_ring);
+    }
+
+    // Rightmost holes first, so each bridge only has to look at the outline and the holes
+    // already joined to its right
+    hole_rings.sort_by(|a, b| rightmost_x(&points, b).total_cmp(&rightmost_x(&points, a)));
+    for hole_ring in &hole_rings {
+        join_hole(&points, &mut ring, hole_ring)?;
+    }
+
+    clip_ears(&points, &ring)
+}
+
+// Turns a convex outline, like the one from convex_hull, into triangles around its first point
+pub fn triangle_fan(outline: &[u32]) -> Vec<u32> {
+    let mut indices = Vec::with_capacity(outline.len().saturating_sub(2) * 3);
+
+    for pair in outline.windows(2).skip(1) {
+        indices.extend_from_slice(&[outline[0], pair[0], pair[1]]);
+    }
+
+    indices
+}
+
+fn clip_ears(points: &[Vector2d], ring: &[usize]) -> Option<Vec<u32>> {
+    let count = ring.len();
+    let mut prev: Vec<usize> = (0..count).map(|i| (i + count - 1) % count).collect();
+    let mut next: Vec<usize> = (0..count).map(|i| (i + 1) % count).collect();
+    let mut triangles = Vec::with_capacity(count.saturating_sub(2) * 3);
+
+    let point = |i: usize| points[ring[i]];
+    let remove = |i: usize, prev: &mut Vec<usize>, next: &mut Vec<usize>| {
+        next[prev[i]] = next[i];
+        prev[next[i]] = prev[i];
+    };
+
+    let mut remaining = count;
+    let mut current = 0;
+    // Vertices looked at since the last one was removed
+    let mut checked = 0;
+
+    while remaining > 3 {
+        let (a, b, c) = (prev[current], current, next[current]);
+
+        if is_ear(points, ring, &next, a, b, c) {
+            triangles.extend_from_slice(&[ring[a] as u32, ring[b] as u32, ring[c] as u32]);
+            remove(b, &mut prev, &mut next);
+            remaining -= 1;
+            checked = 0;
+            current = c;
+        } else if checked > remaining {
+            // No ears left. Collinear and repeated points never are one, drop one of those
+            // and try again, anything else means the outline crosses itself.
+            let mut degenerate = None;
+            let mut i = current;
+            for _ in 0..remaining {
+                if orient(point(prev[i]), point(i), point(next[i])) == 0.0 {
+                    degenerate = Some(i);
+                    break;
+                }
+                i = next[i];
+            }
+
+            let i = degenerate?;
+            remove(i, &mut prev, &mut next);
+            remaining -= 1;
+            checked = 0;
+            current = next[i];
+        } else {
+            checked += 1;
+            current = c;
+        }
+    }
+
+    let (a, b, c) = (prev[current], current, next[current]);
+    if orient(point(a), point(b), point(c)) > 0.0 {
+        triangles.extend_from_slice(&[ring[a] as u32, ring[b] as u32, ring[c] as u32]);
+    }
+
+    Some(triangles)
+}
+
+// a, b, c are positions in the ring with b between a and c
+fn is_ear(
+    points: &[Vector2d],
+    ring: &[usize],
+    next: &[usize],
+    a: usize,
+    b: usize,
+    c: usize,
+) -> bool {
+    let (pa, pb, pc) = (points[ring[a]], points[ring[b]], points[ring[c]]);
+
+    // Reflex corners would cut outside the polygon
+    if orient(pa, pb, pc) <= 0.0 {
+        return false;
+    }
+
+    // Nothing else of the polygon may poke into the triangle. Copies of the corners are
+    // left by the hole bridges and are fine.
+    let mut i = next[c];
+    while i != a {
+        let p = points[ring[i]];
+        if p != pa && p != pb && p != pc && in_triangle(pa, pb, pc, p) {
+            return false;
+        }
+        i = next[i];
+    }
+
+    true
+}
+
+// Splices the hole into the ring with a pair of bridge edges from its rightmost point to a
+// visible ring point (Eberly, Triangulation by Ear Clipping)
+fn join_hole(points: &[Vector2d], ring: &mut Vec<usize>, hole: &[usize]) -> Option<()> {
+    let start = (0..hole.len()).max_by(|&a, &b| points[hole[a]].x.total_cmp(&points[hole[b]].x))?;
+    let m = points[hole[start]];
+
+    // Closest edge hit by a ray from m towards +x
+    let mut hit: Option<(f64, usize)> = None;
+    for i in 0..ring.len() {
+        let (a, b) = (points[ring[i]], points[ring[(i + 1) % ring.len()]]);
+        if a.y == b.y || m.y < a.y.min(b.y) || m.y > a.y.max(b.y) {
+            continue;
+        }
+
+        let t = (m.y - a.y) / (b.y - a.y);
+        let x = a.x + t * (b.x - a.x);
+        if x >= m.x && hit.is_none_or(|(closest, _)| x < closest) {
+            hit = Some((x, i));
+        }
+    }
+
+    let (x, edge) = hit?;
+    let hit_point = Vector2d::new(x, m.y);
+    let (a, b) = (edge, (edge + 1) % ring.len());
+    let mut target = if points[ring[a]].x > points[ring[b]].x {
+        a
+    } else {
+        b
+    };
+
+    // Ring points inside the triangle m, hit, target could block the view to target.
+    // The one closest in angle to the ray can't be blocked itself.
+    let pt = points[ring[target]];
+    if pt != hit_point {
+        let (first, second) = match orient(m, hit_point, pt) > 0.0 {
+            true => (hit_point, pt),
+            false => (pt, hit_point),
+        };
+
+        let mut best = (f64::INFINITY, f64::INFINITY);
+        for (i, &index) in ring.iter().enumerate() {
+            let p = points[index];
+            if p.x <= m.x || !in_triangle(m, first, second, p) {
+                continue;
+            }
+
+            let key = ((p.y - m.y).abs() / (p.x - m.x), p.x - m.x);
+            if key < best {
+                best = key;
+                target = i;
+            }
+        }
+    }
+
+    // Earlier bridges leave two copies of a point, take the one whose corner faces m
+    let target_point = points[ring[target]];
+    if let Some(i) = (0..ring.len())
+        .find(|&i| points[ring[i]] == target_point && locally_inside(points, ring, i, m))
+    {
+        target = i;
+    }
+
+    // target, m, rest of the hole, m, target
+    let mut spliced = Vec::with_capacity(ring.len() + hole.len() + 2);
+    spliced.extend_from_slice(&ring[..=target]);
+    spliced.extend((0..=hole.len()).map(|i| hole[(start + i) % hole.len()]));
+    spliced.extend_from_slice(&ring[target..]);
+    *ring = spliced;
+
+    Some(())
+}
+
+// Whether the direction from ring point i towards p starts inside the polygon
+fn locally_inside(points: &[Vector2d], ring: &[usize], i: usize, p: Vector2d) -> bool {
+    let count = ring.len();
+    let a = points[ring[(i + count - 1) % count]];
+    let v = points[ring[i]];
+    let b = points[ring[(i + 1) % count]];
+
+    if orient(a, v, b) >= 0.0 {
+        orient(v, b, p) >= 0.0 && orient(v, p, a) >= 0.0
+    } else {
+        orient(v, b, p) >= 0.0 || orient(v, p, a) >= 0.0
+    }
+}
+
+fn rightmost_x(points: &[Vector2d], ring: &[usize]) -> f64 {
+    ring.iter()
+        .map(|&i| points[i].x)
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+fn ring_area(points: &[Vector2d], ring: &[usize]) -> f64 {
+    twice_signed_area(ring.iter().map(|&i| points[i]))
+}
+
+fn twice_signed_area(polygon: impl Iterator<Item = Vector2d> + Clone) -> f64 {
+    let first = polygon.clone().next();
+    let mut twice = 0.0;
+    let mut last = first;
+
+    for p in polygon.skip(1).chain(first) {
+        if let Some(a) = last {
+            twice += a.cross(p);
+        }
+        last = Some(p);
+    }
+
+    twice
+}
+
+// Positive when a, b, c turn counter clockwise
+fn orient(a: Vector2d, b: Vector2d, c: Vector2d) -> f64 {
+    (b - a).cross(c - a)
+}
+
+// Inclusive of the edges, a, b, c counter clockwise
+fn in_triangle(a: Vector2d, b: Vector2d, c: Vector2d, p: Vector2d) -> bool {
+    orient(a, b, p) >= 0.0 && orient(b, c, p) >= 0.0 && orient(c, a, p) >= 0.0
+}
+
+// ###########################  CONVEX HULLS  #############################################################
+
+// Quickhull. The hull's corners counter clockwise, starting at the leftmost point, without
+// points that are on its edges. One index when all points are the same, two when they're
+// on a line and none for no points. Use triangle_fan to fill it.
+pub fn convex_hull(points: &[Vector2]) -> Vec<u32> {
+    let points: Vec<Vector2d> = points.iter().map(|p| p.convert()).collect();
+    let all: Vec<usize> = (0..points.len()).collect();
+    let by_position = |&a: &usize, &b: &usize| {
+        points[a]
+            .x
+            .total_cmp(&points[b].x)
+            .then(points[a].y.total_cmp(&points[b].y))
+    };
+
+    let (Some(left), Some(right)) = (
+        all.iter().copied().min_by(by_position),
+        all.iter().copied().max_by(by_position),
+    ) else {
+        return Vec::new();
+    };
+
+    if points[left] == points[right] {
+        return vec![left as u32];
+    }
+
+    let mut hull = vec![left as u32];
+    hull_side(&points, left, right, &all, &mut hull);
+    hull.push(right as u32);
+    hull_side(&points, right, left, &all, &mut hull);
+
+    hull
+}
+
+// Adds the hull points strictly right of a -> b, in order from a to b
+fn hull_side(points: &[Vector2d], a: usize, b: usize, candidates: &[usize], hull: &mut Vec<u32>) {
+    let outside: Vec<usize> = candidates
+        .iter()
+        .copied()
+        .filter(|&i| orient(points[a], points[b], points[i]) < 0.0)
+        .collect();
+
+    let Some(farthest) = outside.iter().copied().min_by(|&i, &j| {
+        orient(points[a], points[b], points[i]).total_cmp(&orient(points[a], points[b], points[j]))
+    }) else {
+        return;
+    };
+
+    hull_side(points, a, farthest, &outside, hull);
+    hull.push(farthest as u32);
+    hull_side(points, farthest, b, &outside, hull);
+}
+
+struct HullFace {
+    corners: [usize; 3],
+    normal: Vector3d,
+    offset: f64,
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+impl HullFace {
+    fn new(points: &[Vector3d], corners: [usize; 3]) -> Self {
+        let [a, b, c] = corners.map(|i| points[i]);
+        let normal = (b - a).cross(c - a).normalized();
+
+        HullFace {
+            corners,
+            normal,
+            offset: normal.dot(a),
+            outside: Vec::new(),
+            alive: true,
+        }
+    }
+
+    fn distance(&self, p: Vector3d) -> f64 {
+        self.normal.dot(p) - self.offset
+    }
+}
+
+// Quickhull. Triangles of the hull with outward facing counter clockwise winding, points
+// inside or within rounding distance of the hull aren't used. None with fewer than 4 points
+// or when they're all on one plane.
+pub fn convex_hull_3d(points: &[Vector3]) -> Option<Vec<u32>> {
+    let points: Vec<Vector3d> = points.iter().map(|p| p.convert()).collect();
+    if points.len() < 4 {
+        return None;
+    }
+
+    // f32 inputs are only so precise, anything closer to a face than this is on it
+    let largest = points
+        .iter()
+        .map(|p| p.x.abs().max(p.y.abs()).max(p.z.abs()))
+        .fold(0.0, f64::max);
+    let epsilon = 4.0 * f32::EPSILON as f64 * largest.max(f32::MIN_POSITIVE as f64);
+
+    let simplex = initial_simplex(&points, epsilon)?;
+    let mut faces: Vec<HullFace> = [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]]
+        .iter()
+        .map(|&[a, b, c]| {
+            let opposite = points[simplex[6 - a - b - c]];
+            let face = HullFace::new(&points, [simplex[a], simplex[b], simplex[c]]);
+
+            // Point the face away from the corner it doesn't use
+            match face.distance(opposite) > 0.0 {
+                true => HullFace::new(&points, [simplex[a], simplex[c], simplex[b]]),
+                false => face,
+            }
+        })
+        .collect();
+
+    let rest: Vec<usize> = (0..points.len()).filter(|i| !simplex.contains(i)).collect();
+    assign_outside(&points, &mut faces, 0, rest, epsilon);
+
+    while let Some(face) = faces.iter().position(|f| f.alive && !f.outside.is_empty()) {
+        let eye = *faces[face].outside.iter().max_by(|&&i, &&j| {
+            faces[face]
+                .distance(points[i])
+                .total_cmp(&faces[face].distance(points[j]))
+        })?;
+        let eye_point = points[eye];
+
+        let visible: Vec<usize> = (0..faces.len())
+            .filter(|&i| faces[i].alive && faces[i].distance(eye_point) > epsilon)
+            .collect();
+
+        // Edges of the visible region that only one visible face uses make the horizon
+        let edges: HashSet<(usize, usize)> = visible
+            .iter()
+            .flat_map(|&i| {
+                let [a, b, c] = faces[i].corners;
+                [(a, b), (b, c), (c, a)]
+            })
+            .collect();
+
+        let mut orphans = Vec::new();
+        for &i in &visible {
+            faces[i].alive = false;
+            orphans.append(&mut faces[i].outside);
+        }
+        orphans.retain(|&i| i != eye);
+
+        // Walked in face order rather than from the set so the output is deterministic
+        let first_new = faces.len();
+        for &i in &visible {
+            let [a, b, c] = faces[i].corners;
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                if !edges.contains(&(to, from)) {
+                    faces.push(HullFace::new(&points, [from, to, eye]));
+                }
+            }
+        }
+
+        assign_outside(&points, &mut faces, first_new, orphans, epsilon);
+    }
+
+    Some(
+        faces
+            .iter()
+            .filter(|face| face.alive)
+            .flat_map(|face| face.corners.map(|i| i as u32))
+            .collect(),
+    )
+}
+
+// Four points spanning a tetrahedron, far apart so the rest start mostly inside
+fn initial_simplex(points: &[Vector3d], epsilon: f64) -> Option<[usize; 4]> {
+    let mut extremes = Vec::with_capacity(6);
+    for axis in 0..3 {
+        let by_axis = |&a: &usize, &b: &usize| points[a][axis].total_cmp(&points[b][axis]);
+        extremes.push((0..points.len()).min_by(by_axis)?);
+        extremes.push((0..points.len()).max_by(by_axis)?);
+    }
+
+    let mut first = (0, 0);
+    let mut longest = 0.0;
+    for &a in &extremes {
+        for &b in &extremes {
+            let distance = points[a].distance(points[b]);
+            if distance > longest {
+                longest = distance;
+                first = (a, b);
+            }
+        }
+    }
+
+    let (a, b) = first;
+    if longest <= epsilon {
+        return None;
+    }
+
+    let direction = (points[b] - points[a]).normalized();
+    let from_line = |i: usize| (points[i] - points[a]).cross(direction).length();
+    let c = (0..points.len()).max_by(|&i, &j| from_line(i).total_cmp(&from_line(j)))?;
+    if from_line(c) <= epsilon {
+        return None;
+    }
+
+    let normal = (points[b] - points[a])
+        .cross(points[c] - points[a])
+        .normalized();
+    let from_plane = |i: usize| normal.dot(points[i] - points[a]).abs();
+    let d = (0..points.len()).max_by(|&i, &j| from_plane(i).total_cmp(&from_plane(j)))?;
+    if from_plane(d) <= epsilon {
+        return None;
+    }
+
+    Some([a, b, c, d])
+}
+
+// Gives each point to the first face from first_face on that it's in front of, points in
+// front of none are inside the hull and dropped
+fn assign_outside(
+    points: &[Vector3d],
+    faces: &mut [HullFace],
+    first_face: usize,
+    candidates: Vec<usize>,
+    epsilon: f64,
+) {
+    for i in candidates {
+        if let Some(face) = faces[first_face..]
+            .iter_mut()
+            .find(|face| face.distance(points[i]) > epsilon)
+        {
+            face.outside.push(i);
+        }
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod polygon_tests {
+    use std::collections::HashMap;
+
+    use crate::math::packing::as_bytes;
+    use crate::math::polygon::{
+        area, contains_point, convex_hull, convex_hull_3d, signed_area, triangle_fan, triangulate,
+        triangulate_with_holes, winding, Winding,
+    };
+    use crate::math::sampling::{sample_sphere, Pcg32};
+    use crate::math::{Vector2, Vector3};
+
+    fn square(center: Vector2, half: f32) -> Vec<Vector2> {
+        vec![
+            center + Vector2::new(-half, -half),
+            center + Vector2::new(half, -half),
+            center + Vector2::new(half, half),
+            center + Vector2::new(-half, half),
+        ]
+    }
+
+    // The polygon the indices refer to, with holes appended like triangulate_with_holes
+    fn check_triangles(points: &[Vector2], indices: &[u32], expected_area: f32) {
+        assert_eq!(indices.len() % 3, 0);
+
+        let mut total = 0.0;
+        for triangle in indices.chunks(3) {
+            let corners: Vec<Vector2> = triangle.iter().map(|&i| points[i as usize]).collect();
+            let triangle_area = signed_area(&corners);
+            assert!(
+                triangle_area > 0.0,
+                "triangle {:?} isn't counter clockwise",
+                triangle
+            );
+            total += triangle_area;
+        }
+
+        assert!((total - expected_area).abs() < 1e-4 * expected_area.max(1.0));
+    }
+
+    #[test]
+    fn test_area_and_winding() {
+        let ccw = square(Vector2::zero(), 1.0);
+        assert_eq!(signed_area(&ccw), 4.0);
+        assert_eq!(winding(&ccw), Some(Winding::CounterClockwise));
+
+        let cw: Vec<Vector2> = ccw.iter().rev().copied().collect();
+        assert_eq!(signed_area(&cw), -4.0);
+        assert_eq!(area(&cw), 4.0);
+        assert_eq!(winding(&cw), Some(Winding::Clockwise));
+
+        let line = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(2.0, 2.0),
+        ];
+        assert_eq!(winding(&line), None);
+        assert_eq!(signed_area(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_contains_point() {
+        // L shape
+        let l = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(2.0, 1.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(1.0, 2.0),
+            Vector2::new(0.0, 2.0),
+        ];
+        assert!(contains_point(&l, Vector2::new(0.5, 0.5)));
+        assert!(contains_point(&l, Vector2::new(1.5, 0.5)));
+        assert!(contains_point(&l, Vector2::new(0.5, 1.5)));
+        assert!(!contains_point(&l, Vector2::new(1.5, 1.5)));
+        assert!(!contains_point(&l, Vector2::new(-0.5, 0.5)));
+
+        let reversed: Vec<Vector2> = l.iter().rev().copied().collect();
+        assert!(contains_point(&reversed, Vector2::new(0.5, 1.5)));
+        assert!(!contains_point(&[], Vector2::zero()));
+    }
+
+    #[test]
+    fn test_triangulate_convex_and_concave() {
+        let quad = square(Vector2::zero(), 1.0);
+        let indices = triangulate(&quad).unwrap();
+        assert_eq!(indices.len(), 6);
+        check_triangles(&quad, &indices, 4.0);
+
+        // A star, every other point is reflex
+        let star: Vec<Vector2> = (0..10)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::TAU / 10.0;
+                let radius = if i % 2 == 0 { 2.0 } else { 0.8 };
+                Vector2::new(angle.cos(), angle.sin()) * radius
+            })
+            .collect();
+        let indices = triangulate(&star).unwrap();
+        assert_eq!(indices.len(), (star.len() - 2) * 3);
+        check_triangles(&star, &indices, area(&star));
+
+        // Triangles stay inside a concave outline
+        for triangle in indices.chunks(3) {
+            let centroid = triangle
+                .iter()
+                .fold(Vector2::zero(), |sum, &i| sum + star[i as usize])
+                / 3.0;
+            assert!(contains_point(&star, centroid));
+        }
+
+        // Clockwise input still gives counter clockwise triangles
+        let cw: Vec<Vector2> = star.iter().rev().copied().collect();
+        check_triangles(&cw, &triangulate(&cw).unwrap(), area(&star));
+    }
+
+    #[test]
+    fn test_triangulate_degenerate() {
+        assert_eq!(
+            triangulate(&[Vector2::zero(), Vector2::new(1.0, 0.0)]),
+            None
+        );
+
+        // Collinear points on an edge are skipped
+        let with_midpoints = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(2.0, 2.0),
+            Vector2::new(1.0, 2.0),
+            Vector2::new(0.0, 2.0),
+        ];
+        check_triangles(&with_midpoints, &triangulate(&with_midpoints).unwrap(), 4.0);
+    }
+
+    #[test]
+    fn test_triangulate_with_holes() {
+        let outline = square(Vector2::zero(), 4.0);
+        let left = square(Vector2::new(-2.0, 0.0), 1.0);
+        // Wound the other way on purpose
+        let right: Vec<Vector2> = square(Vector2::new(2.0, 1.0), 1.0)
+            .into_iter()
+            .rev()
+            .collect();
+
+        let indices = triangulate_with_holes(&outline, &[&left, &right]).unwrap();
+        let all: Vec<Vector2> = outline.iter().chain(&left).chain(&right).copied().collect();
+        check_triangles(&all, &indices, 64.0 - 4.0 - 4.0);
+
+        // Every hole point is used and no triangle covers a hole
+        for i in 0..all.len() as u32 {
+            assert!(indices.contains(&i));
+        }
+        for triangle in indices.chunks(3) {
+            let centroid = triangle
+                .iter()
+                .fold(Vector2::zero(), |sum, &i| sum + all[i as usize])
+                / 3.0;
+            assert!(!contains_point(&left, centroid) && !contains_point(&right, centroid));
+        }
+
+        // Holes lined up with each other share the bridge ray
+        let holes = [
+            square(Vector2::new(-2.0, 0.0), 0.5),
+            square(Vector2::new(0.0, 0.0), 0.5),
+            square(Vector2::new(2.0, 0.0), 0.5),
+        ];
+        let hole_refs: Vec<&[Vector2]> = holes.iter().map(|hole| hole.as_slice()).collect();
+        let indices = triangulate_with_holes(&outline, &hole_refs).unwrap();
+        let all: Vec<Vector2> = outline
+            .iter()
+            .chain(holes.iter().flatten())
+            .copied()
+            .collect();
+        check_triangles(&all, &indices, 64.0 - 3.0);
+
+        assert_eq!(triangulate_with_holes(&outline, &[&left[..2]]), None);
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let mut rng = Pcg32::new(7);
+        let mut points: Vec<Vector2> = (0..200)
+            .map(|_| rng.next_vector2() * 2.0 - Vector2::splat(1.0))
+            .collect();
+        points.extend(square(Vector2::zero(), 1.5));
+        // A point on an edge isn't a corner
+        points.push(Vector2::new(0.0, -1.5));
+
+        let hull = convex_hull(&points);
+        assert_eq!(hull, vec![200, 201, 202, 203]);
+
+        let outline: Vec<Vector2> = hull.iter().map(|&i| points[i as usize]).collect();
+        assert_eq!(winding(&outline), Some(Winding::CounterClockwise));
+
+        let fan = triangle_fan(&hull);
+        assert_eq!(fan, vec![200, 201, 202, 200, 202, 203]);
+        check_triangles(&points, &fan, 9.0);
+
+        assert_eq!(convex_hull(&[]), Vec::<u32>::new());
+        assert_eq!(
+            convex_hull(&[Vector2::splat(1.0), Vector2::splat(1.0)]),
+            vec![0]
+        );
+        let line = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(2.0, 2.0),
+            Vector2::new(1.0, 1.0),
+        ];
+        assert_eq!(convex_hull(&line), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_convex_hull_3d_cube() {
+        let mut points = Vec::new();
+        for i in 0..8 {
+            points.push(Vector3::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            ));
+        }
+        let mut rng = Pcg32::new(3);
+        for _ in 0..100 {
+            points.push(rng.next_vector3() * 1.8 - Vector3::splat(0.9));
+        }
+
+        let indices = convex_hull_3d(&points).unwrap();
+        assert_eq!(indices.len(), 12 * 3);
+        assert!(indices.iter().all(|&i| i < 8));
+
+        // Facing outwards
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| points[triangle[k] as usize]);
+            let normal = (b - a).cross(c - a);
+            assert!(normal.dot(a + b + c) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_convex_hull_3d_sphere() {
+        let mut rng = Pcg32::new(11);
+        let points: Vec<Vector3> = (0..300)
+            .map(|_| sample_sphere(rng.next_vector2()) * 5.0)
+            .collect();
+        let indices = convex_hull_3d(&points).unwrap();
+
+        // Closed surface, every edge is used once in each direction
+        let mut edges = HashMap::new();
+        for triangle in indices.chunks(3) {
+            for k in 0..3 {
+                *edges
+                    .entry((triangle[k], triangle[(k + 1) % 3]))
+                    .or_insert(0) += 1;
+            }
+        }
+        for (&(a, b), &count) in &edges {
+            assert_eq!(count, 1);
+            assert_eq!(edges.get(&(b, a)), Some(&1));
+        }
+
+        // Euler's formula for a closed triangle mesh, F = 2V - 4
+        let mut used: Vec<u32> = indices.clone();
+        used.sort();
+        used.dedup();
+        assert_eq!(indices.len() / 3, 2 * used.len() - 4);
+
+        // Every point is on or behind every face
+        for triangle in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| points[triangle[k] as usize]);
+            let normal = (b - a).cross(c - a).normalized();
+            for &p in &points {
+                assert!(normal.dot(p - a) < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_convex_hull_3d_degenerate() {
+        let flat: Vec<Vector3> = square(Vector2::zero(), 1.0)
+            .iter()
+            .map(|p| p.extend(2.0))
+            .collect();
+        assert_eq!(convex_hull_3d(&flat), None);
+        assert_eq!(convex_hull_3d(&flat[..3]), None);
+    }
+
+    #[test]
+    fn test_indices_as_bytes() {
+        // The index buffer goes to Buffer::buffer_data through packing::as_bytes
+        let indices = triangulate(&square(Vector2::new(0.0, 0.0), 1.0)).unwrap();
+        let bytes = as_bytes(&indices);
+        assert_eq!(bytes.len(), 6 * 4);
+        assert_eq!(bytes[20..], indices[5].to_ne_bytes());
+    }
+}
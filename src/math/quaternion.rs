@@ -0,0 +1,483 @@
+use std::ops::{Add, Mul, MulAssign, Neg};
+
+use crate::math::{Matrix3, Matrix4, Rad, Vector3};
+
+// Order the euler rotations are applied in, XYZ rotates about X first, then Y, then Z.
+// All rotations are about the fixed world axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EulerOrder {
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+}
+
+// x, y, z is the vector part, w is the scalar part
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Quaternion {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Quaternion {
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    pub fn identity() -> Quaternion {
+        Quaternion::new(0.0, 0.0, 0.0, 1.0)
+    }
+
+    // @params: axis must be normalized, angle is right handed and takes Rad or Deg
+    pub fn from_axis_angle(axis: Vector3, angle: impl Into<Rad>) -> Quaternion {
+        let (sin, cos) = (angle.into() / 2.0).sin_cos();
+        Quaternion::new(axis.x * sin, axis.y * sin, axis.z * sin, cos)
+    }
+
+    // @params: x, y and z are the angles about each axis, each takes Rad or Deg
+    pub fn from_euler(
+        x: impl Into<Rad>,
+        y: impl Into<Rad>,
+        z: impl Into<Rad>,
+        order: EulerOrder,
+    ) -> Quaternion {
+        let qx = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), x);
+        let qy = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), y);
+        let qz = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), z);
+
+        // The rotation applied first goes on the right
+        match order {
+            EulerOrder::XYZ => qz * qy * qx,
+            EulerOrder::XZY => qy * qz * qx,
+            EulerOrder::YXZ => qz * qx * qy,
+            EulerOrder::YZX => qx * qz * qy,
+            EulerOrder::ZXY => qy * qx * qz,
+            EulerOrder::ZYX => qx * qy * qz,
+        }
+    }
+
+    // Shortest rotation that turns from into to
+    // @params: from and to must be normalized
+    pub fn from_rotation_arc(from: Vector3, to: Vector3) -> Quaternion {
+        let d = from.dot(to);
+
+        if d < -0.999999 {
+            // Opposite vectors, any axis perpendicular to from works
+            let mut axis = Vector3::new(1.0, 0.0, 0.0).cross(from);
+            if axis.length_squared() < 1e-6 {
+                axis = Vector3::new(0.0, 1.0, 0.0).cross(from);
+            }
+
+            return Quaternion::from_axis_angle(axis.normalized(), Rad(std::f32::consts::PI));
+        }
+
+        let c = from.cross(to);
+        Quaternion::new(c.x, c.y, c.z, 1.0 + d).normalized()
+    }
+
+    // Rotation part of the matrix, it must not contain scale
+    pub fn from_matrix3(m: Matrix3) -> Quaternion {
+        let trace = m.x.x + m.y.y + m.z.z;
+
+        if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Quaternion::new(
+                (m.y.z - m.z.y) / s,
+                (m.z.x - m.x.z) / s,
+                (m.x.y - m.y.x) / s,
+                0.25 * s,
+            )
+        } else if m.x.x > m.y.y && m.x.x > m.z.z {
+            let s = (1.0 + m.x.x - m.y.y - m.z.z).sqrt() * 2.0;
+            Quaternion::new(
+                0.25 * s,
+                (m.y.x + m.x.y) / s,
+                (m.z.x + m.x.z) / s,
+                (m.y.z - m.z.y) / s,
+            )
+        } else if m.y.y > m.z.z {
+            let s = (1.0 + m.y.y - m.x.x - m.z.z).sqrt() * 2.0;
+            Quaternion::new(
+                (m.y.x + m.x.y) / s,
+                0.25 * s,
+                (m.z.y + m.y.z) / s,
+                (m.z.x - m.x.z) / s,
+            )
+        } else {
+            let s = (1.0 + m.z.z - m.x.x - m.y.y).sqrt() * 2.0;
+            Quaternion::new(
+                (m.z.x + m.x.z) / s,
+                (m.z.y + m.y.z) / s,
+                0.25 * s,
+                (m.x.y - m.y.x) / s,
+            )
+        }
+    }
+
+    // Ignores the translation of m
+    pub fn from_matrix4(m: Matrix4) -> Quaternion {
+        Quaternion::from_matrix3(Matrix3::from_matrix4(m))
+    }
+
+    // @params: self must be normalized
+    #[rustfmt::skip]
+    pub fn to_matrix3(self) -> Matrix3 {
+        let Quaternion { x, y, z, w } = self;
+
+        Matrix3::new(
+            1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y + w * z), 2.0 * (x * z - w * y),
+            2.0 * (x * y - w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z + w * x),
+            2.0 * (x * z + w * y), 2.0 * (y * z - w * x), 1.0 - 2.0 * (x * x + y * y),
+        )
+    }
+
+    // @params: self must be normalized
+    pub fn to_matrix4(self) -> Matrix4 {
+        Matrix4::from_matrix3(self.to_matrix3())
+    }
+
+    pub fn vector(self) -> Vector3 {
+        Vector3::new(self.x, self.y, self.z)
+    }
+
+    pub fn dot(self, other: Quaternion) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    pub fn length(self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    pub fn normalize(&mut self) {
+        *self = self.normalized();
+    }
+
+    pub fn normalized(self) -> Quaternion {
+        self * (1.0 / self.length())
+    }
+
+    pub fn conjugate(self) -> Quaternion {
+        Quaternion::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    // For normalized quaternions this is the same as conjugate().
+    // Returns None for a zero quaternion.
+    pub fn inverse(self) -> Option<Quaternion> {
+        let length_squared = self.length_squared();
+
+        if length_squared == 0.0 {
+            return None;
+        }
+
+        Some(self.conjugate() * (1.0 / length_squared))
+    }
+
+    // @params: self must be normalized
+    pub fn rotate_vector(self, v: Vector3) -> Vector3 {
+        let q = self.vector();
+        let t = q.cross(v) * 2.0;
+
+        v + t * self.w + q.cross(t)
+    }
+
+    // Normalized linear interpolation along the shortest path, cheaper than slerp
+    // but does not rotate at a constant speed
+    pub fn nlerp(self, other: Quaternion, t: f32) -> Quaternion {
+        let other = if self.dot(other) < 0.0 { -other } else { other };
+
+        (self * (1.0 - t) + other * t).normalized()
+    }
+
+    // Spherical linear interpolation along the shortest path
+    // @params: self and other must be normalized
+    pub fn slerp(self, other: Quaternion, t: f32) -> Quaternion {
+        let mut cos_theta = self.dot(other);
+        let mut other = other;
+
+        if cos_theta < 0.0 {
+            other = -other;
+            cos_theta = -cos_theta;
+        }
+
+        // Nearly parallel, sin(theta) would blow up
+        if cos_theta > 0.9995 {
+            return self.nlerp(other, t);
+        }
+
+        let theta = cos_theta.acos();
+        let sin_theta = theta.sin();
+
+        self * (((1.0 - t) * theta).sin() / sin_theta) + other * ((t * theta).sin() / sin_theta)
+    }
+}
+
+impl Add for Quaternion {
+    type Output = Quaternion;
+
+    fn add(self, rhs: Quaternion) -> Quaternion {
+        Quaternion::new(
+            self.x + rhs.x,
+            self.y + rhs.y,
+            self.z + rhs.z,
+            self.w + rhs.w,
+        )
+    }
+}
+
+impl Neg for Quaternion {
+    type Output = Quaternion;
+
+    fn neg(self) -> Quaternion {
+        Quaternion::new(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+impl Mul<f32> for Quaternion {
+    type Output = Quaternion;
+
+    fn mul(self, rhs: f32) -> Quaternion {
+        Quaternion::new(self.x * rhs, self.y * rhs, self.z * rhs, self.w * rhs)
+    }
+}
+
+// Hamilton product, rhs is applied first
+impl Mul for Quaternion {
+    type Output = Quaternion;
+
+    fn mul(self, rhs: Quaternion) -> Quaternion {
+        Quaternion::new(
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+        )
+    }
+}
+
+impl MulAssign for Quaternion {
+    fn mul_assign(&mut self, rhs: Quaternion) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<Vector3> for Quaternion {
+    type Output = Vector3;
+
+    fn mul(self, rhs: Vector3) -> Vector3 {
+        self.rotate_vector(rhs)
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod quaternion_tests {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    use crate::assert_abs_diff_eq;
+    use crate::math::*;
+
+    #[test]
+    fn test_identity() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+
+        assert_eq!(Quaternion::identity() * v, v);
+        assert_eq!(Quaternion::identity().to_matrix3(), Matrix3::identity());
+    }
+
+    #[test]
+    fn test_from_axis_angle_matches_rotate() {
+        let q = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), Rad(0.7));
+
+        let mut expected = Vector4::new(5.0, 5.0, 9.0, 1.0);
+        expected.rotate(Axis::X, Rad(0.7));
+
+        assert_abs_diff_eq!(q * Vector3::new(5.0, 5.0, 9.0), expected.xyz(), 1e-5);
+    }
+
+    #[test]
+    fn test_rotate_vector() {
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), Rad(FRAC_PI_2));
+
+        assert_abs_diff_eq!(
+            q * Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            1e-5
+        );
+    }
+
+    #[test]
+    fn test_multiplication_order() {
+        let qx = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), Rad(FRAC_PI_2));
+        let qz = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), Rad(FRAC_PI_2));
+        let v = Vector3::new(0.0, 1.0, 0.0);
+
+        // qx is applied first: y -> z, then z stays z
+        assert_abs_diff_eq!((qz * qx) * v, qz * (qx * v), 1e-5);
+        assert_abs_diff_eq!((qz * qx) * v, Vector3::new(0.0, 0.0, 1.0), 1e-5);
+    }
+
+    #[test]
+    fn test_from_euler() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        let (x, y, z) = (0.3, -1.1, 2.0);
+
+        let qx = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), Rad(x));
+        let qy = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), Rad(y));
+        let qz = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), Rad(z));
+
+        assert_abs_diff_eq!(
+            Quaternion::from_euler(Rad(x), Rad(y), Rad(z), EulerOrder::XYZ) * v,
+            qz * (qy * (qx * v)),
+            1e-5
+        );
+        assert_abs_diff_eq!(
+            Quaternion::from_euler(Rad(x), Rad(y), Rad(z), EulerOrder::ZYX) * v,
+            qx * (qy * (qz * v)),
+            1e-5
+        );
+        assert_abs_diff_eq!(
+            Quaternion::from_euler(Rad(x), Rad(y), Rad(z), EulerOrder::YXZ) * v,
+            qz * (qx * (qy * v)),
+            1e-5
+        );
+    }
+
+    #[test]
+    fn test_from_rotation_arc() {
+        let from = Vector3::new(1.0, 0.0, 0.0);
+        let to = Vector3::new(0.0, 0.6, 0.8);
+
+        assert_abs_diff_eq!(Quaternion::from_rotation_arc(from, to) * from, to, 1e-5);
+        assert_abs_diff_eq!(Quaternion::from_rotation_arc(from, from) * from, from, 1e-5);
+    }
+
+    #[test]
+    fn test_from_rotation_arc_opposite() {
+        let from = Vector3::new(1.0, 0.0, 0.0);
+        let q = Quaternion::from_rotation_arc(from, -from);
+
+        assert_abs_diff_eq!(q * from, -from, 1e-5);
+
+        let from = Vector3::new(0.0, 0.0, -1.0);
+        assert_abs_diff_eq!(
+            Quaternion::from_rotation_arc(from, -from) * from,
+            -from,
+            1e-5
+        );
+    }
+
+    #[test]
+    fn test_conjugate_and_inverse() {
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), Rad(1.2));
+        let v = Vector3::new(1.0, 2.0, 3.0);
+
+        assert_abs_diff_eq!(q.conjugate() * (q * v), v, 1e-5);
+        assert_abs_diff_eq!(q * q.inverse().unwrap(), Quaternion::identity(), 1e-5);
+
+        let scaled = q * 2.0;
+        assert_abs_diff_eq!(
+            (scaled * scaled.inverse().unwrap()).normalized(),
+            Quaternion::identity(),
+            1e-5
+        );
+
+        assert_eq!(Quaternion::new(0.0, 0.0, 0.0, 0.0).inverse(), None);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut q = Quaternion::new(1.0, 2.0, 3.0, 4.0);
+        q.normalize();
+
+        assert!((q.length() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_slerp() {
+        let a = Quaternion::identity();
+        let b = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), Rad(FRAC_PI_2));
+
+        assert_abs_diff_eq!(a.slerp(b, 0.0), a, 1e-5);
+        assert_abs_diff_eq!(a.slerp(b, 1.0), b, 1e-5);
+        assert_abs_diff_eq!(
+            a.slerp(b, 0.5),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), Rad(FRAC_PI_2 / 2.0)),
+            1e-5
+        );
+    }
+
+    #[test]
+    fn test_slerp_shortest_path() {
+        let a = Quaternion::identity();
+        let b = -Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), Rad(0.5));
+
+        assert_abs_diff_eq!(
+            a.slerp(b, 0.5),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), Rad(0.25)),
+            1e-5
+        );
+    }
+
+    #[test]
+    fn test_nlerp() {
+        let a = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), Rad(0.2));
+        let b = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), Rad(0.6));
+
+        assert_abs_diff_eq!(a.nlerp(b, 0.0), a, 1e-5);
+        assert_abs_diff_eq!(a.nlerp(b, 1.0), b, 1e-5);
+        assert_abs_diff_eq!(
+            a.nlerp(b, 0.5),
+            Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), Rad(0.4)),
+            1e-5
+        );
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let axis = Vector3::new(1.0, 2.0, -0.5).normalized();
+
+        for angle in [0.1, 1.0, 2.5, PI - 0.01, -2.0] {
+            let q = Quaternion::from_axis_angle(axis, Rad(angle));
+
+            assert_abs_diff_eq!(Quaternion::from_matrix3(q.to_matrix3()), q, 1e-5);
+            assert_abs_diff_eq!(Quaternion::from_matrix4(q.to_matrix4()), q, 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_to_matrix_matches_rotation() {
+        let q = Quaternion::from_euler(Rad(0.4), Rad(1.3), Rad(-0.8), EulerOrder::XYZ);
+        let v = Vector3::new(3.0, -1.0, 2.0);
+
+        assert_abs_diff_eq!(q.to_matrix3() * v, q * v, 1e-5);
+        assert_abs_diff_eq!((q.to_matrix4() * v.extend(1.0)).xyz(), q * v, 1e-5);
+    }
+
+    #[test]
+    fn test_from_matrix_of_axis_rotations() {
+        // Exercise each branch of from_matrix3
+        for axis in [
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ] {
+            let q = Quaternion::from_axis_angle(axis, Rad(PI));
+            assert_abs_diff_eq!(
+                Quaternion::from_matrix3(q.to_matrix3()).to_matrix3(),
+                q.to_matrix3(),
+                1e-5
+            );
+        }
+    }
+}
@@ -0,0 +1,513 @@
+// Random numbers and well distributed sample sets for rendering kernels (SSAO, soft shadows,
+// IBL prefiltering). The generator and the sequences are pure integer math and give the same
+// bits everywhere. Warps that use sin/cos can differ in the last bit between platforms.
+use std::f32::consts::{FRAC_PI_4, PI, TAU};
+
+use crate::math::{Vector2, Vector3};
+
+// PCG32 (XSH RR) by Melissa O'Neill, small, fast and statistically good.
+// Not for anything cryptographic.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg32 {
+    const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+    const DEFAULT_STREAM: u64 = 0xda3e_39cb_94b9_5bdb;
+
+    pub fn new(seed: u64) -> Self {
+        Self::with_stream(seed, Self::DEFAULT_STREAM)
+    }
+
+    // Generators with the same seed but different streams give unrelated sequences
+    pub fn with_stream(seed: u64, stream: u64) -> Self {
+        let mut rng = Self {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+
+        rng
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old
+            .wrapping_mul(Self::MULTIPLIER)
+            .wrapping_add(self.increment);
+
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        (self.next_u32() as u64) << 32 | self.next_u32() as u64
+    }
+
+    // Uniform in [0, bound) without modulo bias. Panics if bound is 0.
+    pub fn next_below(&mut self, bound: u32) -> u32 {
+        if bound == 0 {
+            panic!("Bound provided to Pcg32::next_below must be greater than 0!");
+        }
+
+        // Rejects the values below 2^32 % bound so every remainder is equally likely
+        let threshold = bound.wrapping_neg() % bound;
+
+        loop {
+            let value = self.next_u32();
+            if value >= threshold {
+                return value % bound;
+            }
+        }
+    }
+
+    // Uniform in [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        to_unit_float(self.next_u32())
+    }
+
+    // Uniform in [min, max)
+    pub fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    // Uniform in the unit square, feed into the warps below
+    pub fn next_vector2(&mut self) -> Vector2 {
+        Vector2::new(self.next_f32(), self.next_f32())
+    }
+
+    // Uniform in the unit cube
+    pub fn next_vector3(&mut self) -> Vector3 {
+        Vector3::new(self.next_f32(), self.next_f32(), self.next_f32())
+    }
+}
+
+// [0, 1) from the top 24 bits, every result is exactly representable
+fn to_unit_float(bits: u32) -> f32 {
+    (bits >> 8) as f32 / (1 << 24) as f32
+}
+
+// ###########################  LOW DISCREPANCY SEQUENCES  ################################################
+
+// Base 2 radical inverse, the bits of index mirrored around the binary point
+pub fn van_der_corput(index: u32) -> f32 {
+    to_unit_float(index.reverse_bits())
+}
+
+// Radical inverse of index in any base >= 2, dimension `base` of the Halton sequence
+pub fn radical_inverse(mut index: u32, base: u32) -> f32 {
+    if base < 2 {
+        panic!("Base provided to radical_inverse must be at least 2!");
+    }
+
+    let inverse_base = 1.0 / base as f64;
+    let mut factor = inverse_base;
+    let mut result = 0.0f64;
+
+    while index > 0 {
+        result += (index % base) as f64 * factor;
+        index /= base;
+        factor *= inverse_base;
+    }
+
+    // Rounding to f32 could land on 1.0
+    (result as f32).min(1.0 - f32::EPSILON / 2.0)
+}
+
+// Point index of a set of count points, needs count up front but is evenly spread for any count
+pub fn hammersley(index: u32, count: u32) -> Vector2 {
+    Vector2::new(index as f32 / count as f32, van_der_corput(index))
+}
+
+// Halton sequence in bases 2 and 3, can be extended one point at a time
+pub fn halton2(index: u32) -> Vector2 {
+    Vector2::new(van_der_corput(index), radical_inverse(index, 3))
+}
+
+// Halton sequence in bases 2, 3 and 5
+pub fn halton3(index: u32) -> Vector3 {
+    Vector3::new(
+        van_der_corput(index),
+        radical_inverse(index, 3),
+        radical_inverse(index, 5),
+    )
+}
+
+// First two dimensions of the Sobol sequence. Every power of two prefix is a (0, 2) net:
+// for 2^k points each of the 2^k equal rectangles of area 2^-k holds exactly one point.
+pub fn sobol2(index: u32) -> Vector2 {
+    let mut bits = index;
+    let mut direction = 1u32 << 31;
+    let mut second = 0;
+
+    while bits != 0 {
+        if bits & 1 != 0 {
+            second ^= direction;
+        }
+        bits >>= 1;
+        direction ^= direction >> 1;
+    }
+
+    Vector2::new(van_der_corput(index), to_unit_float(second))
+}
+
+// ###########################  WARPS  ####################################################################
+// All take a point in the unit square, from Pcg32::next_vector2 or one of the sequences,
+// and keep its distribution: stratified inputs give stratified outputs.
+
+// Uniform in the unit disk with Shirley and Chiu's concentric mapping
+pub fn sample_disk(u: Vector2) -> Vector2 {
+    let x = 2.0 * u.x - 1.0;
+    let y = 2.0 * u.y - 1.0;
+
+    if x == 0.0 && y == 0.0 {
+        return Vector2::zero();
+    }
+
+    let (radius, angle) = if x.abs() > y.abs() {
+        (x, FRAC_PI_4 * (y / x))
+    } else {
+        (y, PI / 2.0 - FRAC_PI_4 * (x / y))
+    };
+
+    let (sin, cos) = angle.sin_cos();
+    Vector2::new(radius * cos, radius * sin)
+}
+
+// Uniform on the unit sphere
+pub fn sample_sphere(u: Vector2) -> Vector3 {
+    let z = 1.0 - 2.0 * u.x;
+    let radius = (1.0 - z * z).max(0.0).sqrt();
+    let (sin, cos) = (TAU * u.y).sin_cos();
+
+    Vector3::new(radius * cos, radius * sin, z)
+}
+
+// Uniform on the hemisphere around +z, see orient_to_normal
+pub fn sample_hemisphere(u: Vector2) -> Vector3 {
+    let z = u.x;
+    let radius = (1.0 - z * z).max(0.0).sqrt();
+    let (sin, cos) = (TAU * u.y).sin_cos();
+
+    Vector3::new(radius * cos, radius * sin, z)
+}
+
+// Hemisphere around +z with density proportional to the cosine to +z (Malley's method),
+// importance samples diffuse lighting
+pub fn sample_cosine_hemisphere(u: Vector2) -> Vector3 {
+    let disk = sample_disk(u);
+    let z = (1.0 - disk.length_squared()).max(0.0).sqrt();
+
+    Vector3::new(disk.x, disk.y, z)
+}
+
+// Probability densities of the warps, per unit area or solid angle
+pub const DISK_PDF: f32 = 1.0 / PI;
+pub const SPHERE_PDF: f32 = 1.0 / (4.0 * PI);
+pub const HEMISPHERE_PDF: f32 = 1.0 / (2.0 * PI);
+
+pub fn cosine_hemisphere_pdf(cos_theta: f32) -> f32 {
+    cos_theta / PI
+}
+
+// Rotates a sample around +z so +z maps to normal, which must be normalized.
+// Branchless orthonormal basis from Duff et al. 2017.
+pub fn orient_to_normal(sample: Vector3, normal: Vector3) -> Vector3 {
+    let sign = 1.0f32.copysign(normal.z);
+    let a = -1.0 / (sign + normal.z);
+    let b = normal.x * normal.y * a;
+    let tangent = Vector3::new(
+        1.0 + sign * normal.x * normal.x * a,
+        sign * b,
+        -sign * normal.x,
+    );
+    let bitangent = Vector3::new(b, sign + normal.y * normal.y * a, -normal.y);
+
+    tangent * sample.x + bitangent * sample.y + normal * sample.z
+}
+
+// ###########################  POISSON DISK  #############################################################
+
+// Points in [0, size.x) x [0, size.y) no closer than min_distance to each other, with
+// Bridson's algorithm. Denser and more even than rejection sampling, about 0.7 points per
+// min_distance squared.
+pub fn poisson_disk(rng: &mut Pcg32, size: Vector2, min_distance: f32) -> Vec<Vector2> {
+    // Candidates tried around each active point before it's retired
+    const ATTEMPTS: u32 = 30;
+
+    if size.x <= 0.0 || size.y <= 0.0 || min_distance <= 0.0 {
+        return Vec::new();
+    }
+
+    // A cell is small enough to hold at most one point
+    let cell_size = min_distance / std::f32::consts::SQRT_2;
+    let columns = (size.x / cell_size).ceil() as usize;
+    let rows = (size.y / cell_size).ceil() as usize;
+    let mut grid: Vec<Option<usize>> = vec![None; columns * rows];
+    let cell = |p: Vector2| {
+        let column = ((p.x / cell_size) as usize).min(columns - 1);
+        let row = ((p.y / cell_size) as usize).min(rows - 1);
+        (column, row)
+    };
+
+    let mut points = Vec::new();
+    let mut active = Vec::new();
+
+    let first = Vector2::new(rng.range_f32(0.0, size.x), rng.range_f32(0.0, size.y));
+    let (column, row) = cell(first);
+    grid[row * columns + column] = Some(0);
+    points.push(first);
+    active.push(0);
+
+    while !active.is_empty() {
+        let slot = rng.next_below(active.len() as u32) as usize;
+        let center = points[active[slot]];
+        let mut found = false;
+
+        for _ in 0..ATTEMPTS {
+            // Uniform in the annulus between min_distance and twice that
+            let radius = min_distance * (1.0 + 3.0 * rng.next_f32()).sqrt();
+            let (sin, cos) = (TAU * rng.next_f32()).sin_cos();
+            let candidate = center + Vector2::new(cos, sin) * radius;
+
+            if candidate.x < 0.0
+                || candidate.y < 0.0
+                || candidate.x >= size.x
+                || candidate.y >= size.y
+            {
+                continue;
+            }
+
+            let (column, row) = cell(candidate);
+            let too_close = (row.saturating_sub(2)..(row + 3).min(rows)).any(|r| {
+                (column.saturating_sub(2)..(column + 3).min(columns)).any(|c| {
+                    grid[r * columns + c].is_some_and(|i| {
+                        (points[i] - candidate).length_squared() < min_distance * min_distance
+                    })
+                })
+            });
+
+            if !too_close {
+                grid[row * columns + column] = Some(points.len());
+                active.push(points.len());
+                points.push(candidate);
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            active.swap_remove(slot);
+        }
+    }
+
+    points
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod sampling_tests {
+    use crate::assert_abs_diff_eq;
+    use crate::math::sampling::{
+        cosine_hemisphere_pdf, halton2, halton3, hammersley, orient_to_normal, poisson_disk,
+        radical_inverse, sample_cosine_hemisphere, sample_disk, sample_hemisphere, sample_sphere,
+        sobol2, van_der_corput, Pcg32,
+    };
+    use crate::math::*;
+
+    #[test]
+    fn test_pcg32_reference_output() {
+        // From the reference pcg32-demo with seed 42 and sequence 54
+        let mut rng = Pcg32::with_stream(42, 54);
+        let expected = [
+            0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e,
+        ];
+
+        for value in expected {
+            assert_eq!(rng.next_u32(), value);
+        }
+    }
+
+    #[test]
+    fn test_pcg32_ranges() {
+        let mut rng = Pcg32::new(1);
+        let mut counts = [0; 6];
+
+        for _ in 0..6000 {
+            let f = rng.next_f32();
+            assert!((0.0..1.0).contains(&f));
+
+            let r = rng.range_f32(-2.0, 3.0);
+            assert!((-2.0..3.0).contains(&r));
+
+            counts[rng.next_below(6) as usize] += 1;
+        }
+
+        // Roughly uniform, each bucket expects 1000
+        assert!(
+            counts.iter().all(|c| (850..1150).contains(c)),
+            "{:?}",
+            counts
+        );
+
+        assert_eq!(Pcg32::new(5), Pcg32::new(5));
+        assert_ne!(Pcg32::new(5).next_u64(), Pcg32::new(6).next_u64());
+        assert_ne!(
+            Pcg32::with_stream(5, 1).next_u32(),
+            Pcg32::with_stream(5, 2).next_u32()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pcg32_zero_bound() {
+        Pcg32::new(1).next_below(0);
+    }
+
+    #[test]
+    fn test_radical_inverse() {
+        let base2: Vec<f32> = (0..5).map(van_der_corput).collect();
+        assert_eq!(base2, vec![0.0, 0.5, 0.25, 0.75, 0.125]);
+
+        for i in 0..100 {
+            assert_eq!(radical_inverse(i, 2), van_der_corput(i));
+        }
+
+        assert_abs_diff_eq!(radical_inverse(1, 3), 1.0 / 3.0, 1e-7);
+        assert_abs_diff_eq!(radical_inverse(2, 3), 2.0 / 3.0, 1e-7);
+        assert_abs_diff_eq!(radical_inverse(3, 3), 1.0 / 9.0, 1e-7);
+        assert_abs_diff_eq!(radical_inverse(7, 5), 0.44, 1e-7);
+        assert!(radical_inverse(u32::MAX, 3) < 1.0);
+    }
+
+    #[test]
+    fn test_sequences() {
+        assert_eq!(hammersley(3, 8), Vector2::new(0.375, 0.75));
+        assert_eq!(halton2(1).x, 0.5);
+        assert_eq!(halton3(4).z, radical_inverse(4, 5));
+
+        let sobol: Vec<Vector2> = (0..4).map(sobol2).collect();
+        assert_eq!(
+            sobol,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(0.5, 0.5),
+                Vector2::new(0.25, 0.75),
+                Vector2::new(0.75, 0.25),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sobol_is_a_net() {
+        // 64 points, every elementary interval of area 1 / 64 has exactly one point
+        let points: Vec<Vector2> = (0..64).map(sobol2).collect();
+
+        for columns_log in 0..=6 {
+            let columns = 1 << columns_log;
+            let rows = 64 / columns;
+            let mut hits = vec![0; 64];
+
+            for p in &points {
+                let column = (p.x * columns as f32) as usize;
+                let row = (p.y * rows as f32) as usize;
+                hits[row * columns + column] += 1;
+            }
+
+            assert!(hits.iter().all(|h| *h == 1), "{} columns", columns);
+        }
+    }
+
+    #[test]
+    fn test_warps() {
+        let count = 4096;
+        let mut hemisphere_z = 0.0;
+        let mut cosine_z = 0.0;
+
+        for i in 0..count {
+            let u = hammersley(i, count);
+
+            assert!(sample_disk(u).length() <= 1.0 + 1e-6);
+            assert_abs_diff_eq!(sample_sphere(u).length(), 1.0, 1e-5);
+
+            let hemisphere = sample_hemisphere(u);
+            assert_abs_diff_eq!(hemisphere.length(), 1.0, 1e-5);
+            assert!(hemisphere.z >= 0.0);
+            hemisphere_z += hemisphere.z;
+
+            let cosine = sample_cosine_hemisphere(u);
+            assert_abs_diff_eq!(cosine.length(), 1.0, 1e-5);
+            assert!(cosine.z >= 0.0);
+            cosine_z += cosine.z;
+        }
+
+        // Mean cosine is 1/2 for uniform and 2/3 for cosine weighted hemispheres
+        assert_abs_diff_eq!(hemisphere_z / count as f32, 0.5, 1e-3);
+        assert_abs_diff_eq!(cosine_z / count as f32, 2.0 / 3.0, 1e-3);
+
+        // Corners of the square land on the edge of the disk
+        assert_abs_diff_eq!(
+            sample_disk(Vector2::new(1.0, 0.5)),
+            Vector2::new(1.0, 0.0),
+            1e-6
+        );
+        assert_eq!(sample_disk(Vector2::splat(0.5)), Vector2::zero());
+        assert_abs_diff_eq!(sample_disk(Vector2::new(0.5, 0.0)).length(), 1.0, 1e-6);
+
+        assert_abs_diff_eq!(cosine_hemisphere_pdf(1.0), 1.0 / std::f32::consts::PI, 1e-7);
+    }
+
+    #[test]
+    fn test_orient_to_normal() {
+        let mut rng = Pcg32::new(3);
+
+        for normal in [
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(0.0, 0.0, -1.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.3, -0.8, 0.2).normalized(),
+        ] {
+            assert_abs_diff_eq!(
+                orient_to_normal(Vector3::new(0.0, 0.0, 1.0), normal),
+                normal,
+                1e-5
+            );
+
+            for _ in 0..20 {
+                let sample = sample_hemisphere(rng.next_vector2());
+                let oriented = orient_to_normal(sample, normal);
+
+                // Lengths and the angle to the normal are kept
+                assert_abs_diff_eq!(oriented.length(), 1.0, 1e-5);
+                assert_abs_diff_eq!(oriented.dot(normal), sample.z, 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_poisson_disk() {
+        let size = Vector2::new(10.0, 6.0);
+        let min_distance = 0.5;
+        let points = poisson_disk(&mut Pcg32::new(9), size, min_distance);
+
+        for (i, a) in points.iter().enumerate() {
+            assert!(a.x >= 0.0 && a.y >= 0.0 && a.x < size.x && a.y < size.y);
+            for b in &points[i + 1..] {
+                assert!((*a - *b).length() >= min_distance);
+            }
+        }
+
+        // Maximal sets cover the area, roughly 0.7 points per min_distance squared
+        let expected = 0.7 * size.x * size.y / (min_distance * min_distance);
+        assert!(points.len() as f32 > expected * 0.8, "{}", points.len());
+
+        assert_eq!(points, poisson_disk(&mut Pcg32::new(9), size, min_distance));
+        assert!(poisson_disk(&mut Pcg32::new(9), size, 0.0).is_empty());
+    }
+}
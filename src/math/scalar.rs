@@ -0,0 +1,145 @@
+use std::fmt::{Debug, Display};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::math::{matrix_mul_matrix, matrix_mul_vector, simd, Matrix, Vector};
+
+// Element type of Vector and Matrix, implemented for f32, f64, i32 and u32.
+// Only meant for plain number types, the raw access functions view them as bytes.
+pub trait Scalar:
+    Copy
+    + PartialEq
+    + PartialOrd
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    // Same as an `as` cast, so floats going to integers truncate and saturate
+    fn from_f64(n: f64) -> Self;
+
+    // Exact for every Scalar type
+    fn to_f64(self) -> f64;
+
+    // Matrix4 products, f32 replaces these with the SIMD versions in simd.rs
+    fn mul_matrix4(a: &Matrix<Self, 4, 4>, b: &Matrix<Self, 4, 4>) -> Matrix<Self, 4, 4> {
+        matrix_mul_matrix(a, b)
+    }
+
+    fn mul_matrix4_vector(m: &Matrix<Self, 4, 4>, v: Vector<Self, 4>) -> Vector<Self, 4> {
+        matrix_mul_vector(m, v)
+    }
+}
+
+// Scalars that the length, normalize, inverse etc. functions work on
+pub trait Float: Scalar + Neg<Output = Self> {
+    const PI: Self;
+    const EPSILON: Self;
+
+    fn sqrt(self) -> Self;
+    fn abs(self) -> Self;
+    fn floor(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+}
+
+macro_rules! impl_scalar {
+    ($($t:ident: $zero:literal, $one:literal $({ $($hooks:item)* })?);+) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: $t = $zero;
+                const ONE: $t = $one;
+
+                $($($hooks)*)?
+
+                fn from_f64(n: f64) -> $t {
+                    n as $t
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_float {
+    ($($t:ident),+) => {
+        $(
+            impl Float for $t {
+                const PI: $t = std::$t::consts::PI;
+                const EPSILON: $t = $t::EPSILON;
+
+                fn sqrt(self) -> $t {
+                    $t::sqrt(self)
+                }
+
+                fn abs(self) -> $t {
+                    $t::abs(self)
+                }
+
+                fn floor(self) -> $t {
+                    $t::floor(self)
+                }
+
+                fn sin(self) -> $t {
+                    $t::sin(self)
+                }
+
+                fn cos(self) -> $t {
+                    $t::cos(self)
+                }
+
+                fn tan(self) -> $t {
+                    $t::tan(self)
+                }
+
+                fn asin(self) -> $t {
+                    $t::asin(self)
+                }
+
+                fn acos(self) -> $t {
+                    $t::acos(self)
+                }
+
+                fn atan2(self, other: $t) -> $t {
+                    $t::atan2(self, other)
+                }
+
+                fn sin_cos(self) -> ($t, $t) {
+                    $t::sin_cos(self)
+                }
+            }
+        )+
+    };
+}
+
+impl_scalar! {
+    f32: 0.0, 1.0 {
+        fn mul_matrix4(a: &Matrix<f32, 4, 4>, b: &Matrix<f32, 4, 4>) -> Matrix<f32, 4, 4> {
+            simd::mul_matrix4(a, b)
+        }
+
+        fn mul_matrix4_vector(m: &Matrix<f32, 4, 4>, v: Vector<f32, 4>) -> Vector<f32, 4> {
+            simd::mul_matrix4_vector(m, v)
+        }
+    };
+    f64: 0.0, 1.0;
+    i32: 0, 1;
+    u32: 0, 1
+}
+impl_float! { f32, f64 }
@@ -0,0 +1,314 @@
+use crate::math::{Matrix4, Ray, Vector2, Vector3, Vector4};
+
+// Same as the glViewport arguments, in pixels with the origin at the bottom left
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+// NDC z range of the projection matrix. Window depth is always [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DepthRange {
+    // OpenGL default, Matrix4::perspective, frustum and orthographic
+    NegativeOneToOne,
+    // glClipControl(..., GL_ZERO_TO_ONE) with near at 0
+    ZeroToOne,
+    // glClipControl(..., GL_ZERO_TO_ONE) with near at 1, Matrix4::perspective_reversed_z
+    ReversedZeroToOne,
+}
+
+impl Viewport {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+impl DepthRange {
+    fn ndc_to_window(self, z: f32) -> f32 {
+        match self {
+            DepthRange::NegativeOneToOne => (z + 1.0) * 0.5,
+            DepthRange::ZeroToOne | DepthRange::ReversedZeroToOne => z,
+        }
+    }
+
+    fn window_to_ndc(self, z: f32) -> f32 {
+        match self {
+            DepthRange::NegativeOneToOne => z * 2.0 - 1.0,
+            DepthRange::ZeroToOne | DepthRange::ReversedZeroToOne => z,
+        }
+    }
+
+    // Window depth of the near plane
+    fn near(self) -> f32 {
+        match self {
+            DepthRange::NegativeOneToOne | DepthRange::ZeroToOne => 0.0,
+            DepthRange::ReversedZeroToOne => 1.0,
+        }
+    }
+}
+
+// World space point to window coordinates (x, y in pixels, z is the depth buffer value).
+// Same as gluProject. Returns None for points on the camera plane (clip w of 0).
+pub fn project(
+    point: Vector3,
+    viewport: &Viewport,
+    view: &Matrix4,
+    projection: &Matrix4,
+    depth: DepthRange,
+) -> Option<Vector3> {
+    let clip = *projection * (*view * point.extend(1.0));
+
+    if clip.w == 0.0 {
+        return None;
+    }
+
+    let ndc = clip.xyz() / clip.w;
+
+    Some(Vector3::new(
+        viewport.x + (ndc.x + 1.0) * 0.5 * viewport.width,
+        viewport.y + (ndc.y + 1.0) * 0.5 * viewport.height,
+        depth.ndc_to_window(ndc.z),
+    ))
+}
+
+// Window coordinates back to world space, the inverse of project. Same as gluUnProject.
+// Returns None if projection * view is singular or the depth maps to infinity.
+pub fn unproject(
+    window: Vector3,
+    viewport: &Viewport,
+    view: &Matrix4,
+    projection: &Matrix4,
+    depth: DepthRange,
+) -> Option<Vector3> {
+    let inverse = (*projection * *view).inverse()?;
+
+    let ndc = Vector4::new(
+        (window.x - viewport.x) / viewport.width * 2.0 - 1.0,
+        (window.y - viewport.y) / viewport.height * 2.0 - 1.0,
+        depth.window_to_ndc(window.z),
+        1.0,
+    );
+
+    let world = inverse * ndc;
+
+    if world.w == 0.0 {
+        return None;
+    }
+
+    Some(world.xyz() / world.w)
+}
+
+// World space picking ray through the cursor, starting on the near plane.
+// @params: cursor is in pixels from the top left of the viewport, like glfw's get_cursor_pos
+// when the viewport covers the whole window. Works with infinite far planes.
+pub fn ray_from_cursor(
+    cursor: Vector2,
+    viewport: &Viewport,
+    view: &Matrix4,
+    projection: &Matrix4,
+    depth: DepthRange,
+) -> Option<Ray> {
+    // Window y goes up from the bottom
+    let x = viewport.x + cursor.x;
+    let y = viewport.y + viewport.height - cursor.y;
+
+    // Halfway is finite even when the far plane is at infinity
+    let near = unproject(
+        Vector3::new(x, y, depth.near()),
+        viewport,
+        view,
+        projection,
+        depth,
+    )?;
+    let middle = unproject(Vector3::new(x, y, 0.5), viewport, view, projection, depth)?;
+
+    Some(Ray::new(near, (middle - near).normalized()))
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod screen_tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::assert_abs_diff_eq;
+    use crate::math::*;
+
+    fn sample_view() -> Matrix4 {
+        Matrix4::look_at_rh(
+            Vector3::new(3.0, 4.0, 10.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )
+    }
+
+    fn sample_viewport() -> Viewport {
+        Viewport::new(10.0, 20.0, 800.0, 600.0)
+    }
+
+    #[test]
+    fn test_project_center() {
+        // Camera at the origin looking down -z, the center of the screen is straight ahead
+        let projection = Matrix4::perspective(Rad(FRAC_PI_2), 800.0 / 600.0, 1.0, 100.0);
+        let window = project(
+            Vector3::new(0.0, 0.0, -1.0),
+            &sample_viewport(),
+            &Matrix4::identity(),
+            &projection,
+            DepthRange::NegativeOneToOne,
+        )
+        .unwrap();
+
+        assert_abs_diff_eq!(window, Vector3::new(410.0, 320.0, 0.0), 1e-3);
+
+        // Same point with a [0, 1] projection is also on the near plane
+        let projection = Matrix4::perspective_reversed_z(Rad(FRAC_PI_2), 800.0 / 600.0, 1.0, 100.0);
+        let window = project(
+            Vector3::new(0.0, 0.0, -1.0),
+            &sample_viewport(),
+            &Matrix4::identity(),
+            &projection,
+            DepthRange::ReversedZeroToOne,
+        )
+        .unwrap();
+
+        assert_abs_diff_eq!(window, Vector3::new(410.0, 320.0, 1.0), 1e-3);
+    }
+
+    #[test]
+    fn test_project_unproject_round_trip() {
+        let point = Vector3::new(0.5, -1.0, 2.0);
+
+        for (projection, depth) in [
+            (
+                Matrix4::perspective(Rad(1.0), 1.3, 0.5, 50.0),
+                DepthRange::NegativeOneToOne,
+            ),
+            (
+                Matrix4::orthographic(-5.0, 5.0, -4.0, 4.0, 0.1, 30.0),
+                DepthRange::NegativeOneToOne,
+            ),
+            (
+                Matrix4::perspective_reversed_z(Rad(1.0), 1.3, 0.5, 50.0),
+                DepthRange::ReversedZeroToOne,
+            ),
+            (
+                Matrix4::perspective_infinite_reversed_z(Rad(1.0), 1.3, 0.5),
+                DepthRange::ReversedZeroToOne,
+            ),
+        ] {
+            let window = project(
+                point,
+                &sample_viewport(),
+                &sample_view(),
+                &projection,
+                depth,
+            )
+            .unwrap();
+            let world = unproject(
+                window,
+                &sample_viewport(),
+                &sample_view(),
+                &projection,
+                depth,
+            )
+            .unwrap();
+
+            assert_abs_diff_eq!(world, point, 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_unproject_singular() {
+        let window = Vector3::new(100.0, 100.0, 0.5);
+        let result = unproject(
+            window,
+            &sample_viewport(),
+            &sample_view(),
+            &Matrix4::from_array([[0.0; 4]; 4]),
+            DepthRange::NegativeOneToOne,
+        );
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_ray_from_cursor_hits_the_projected_point() {
+        let viewport = sample_viewport();
+        let target = Vector3::new(-1.0, 2.0, 0.5);
+
+        for (projection, depth) in [
+            (
+                Matrix4::perspective(Rad(1.0), 800.0 / 600.0, 0.5, 50.0),
+                DepthRange::NegativeOneToOne,
+            ),
+            (
+                Matrix4::perspective_infinite(Rad(1.0), 800.0 / 600.0, 0.5),
+                DepthRange::NegativeOneToOne,
+            ),
+            (
+                Matrix4::perspective_infinite_reversed_z(Rad(1.0), 800.0 / 600.0, 0.5),
+                DepthRange::ReversedZeroToOne,
+            ),
+            (
+                Matrix4::orthographic(-8.0, 8.0, -6.0, 6.0, 0.5, 50.0),
+                DepthRange::NegativeOneToOne,
+            ),
+        ] {
+            let window = project(target, &viewport, &sample_view(), &projection, depth).unwrap();
+
+            // Cursor coordinates start at the top left of the viewport
+            let cursor = Vector2::new(
+                window.x - viewport.x,
+                viewport.y + viewport.height - window.y,
+            );
+
+            let ray =
+                ray_from_cursor(cursor, &viewport, &sample_view(), &projection, depth).unwrap();
+
+            assert!(ray.distance_to_point(target) < 1e-3);
+            // Pointing away from the camera, towards the target
+            assert!((target - ray.origin).dot(ray.direction) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_ray_from_cursor_center() {
+        let viewport = Viewport::new(0.0, 0.0, 640.0, 480.0);
+        let projection = Matrix4::perspective(Rad(1.0), 640.0 / 480.0, 0.1, 100.0);
+        let eye = Vector3::new(0.0, 0.0, 5.0);
+        let view = Matrix4::look_at_rh(eye, Vector3::zero(), Vector3::new(0.0, 1.0, 0.0));
+
+        let ray = ray_from_cursor(
+            Vector2::new(320.0, 240.0),
+            &viewport,
+            &view,
+            &projection,
+            DepthRange::NegativeOneToOne,
+        )
+        .unwrap();
+
+        assert_abs_diff_eq!(ray.origin, Vector3::new(0.0, 0.0, 4.9), 1e-3);
+        assert_abs_diff_eq!(ray.direction, Vector3::new(0.0, 0.0, -1.0), 1e-3);
+
+        // Top left corner points up and to the left
+        let corner = ray_from_cursor(
+            Vector2::new(0.0, 0.0),
+            &viewport,
+            &view,
+            &projection,
+            DepthRange::NegativeOneToOne,
+        )
+        .unwrap();
+        assert!(corner.direction.x < 0.0 && corner.direction.y > 0.0);
+    }
+}
@@ -0,0 +1,310 @@
+// Vectors are written as [x, y, ...] and matrices as an array of columns, [[c0], [c1], ...],
+// the same layout as from_array. Everything else in math derives its impls.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+use crate::math::{ArcLengthTable, BSpline, CatmullRom, Matrix, Vector, VisibilityMask};
+
+impl<T: Serialize, const N: usize> Serialize for Vector<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for Vector<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_array(deserializer).map(Vector)
+    }
+}
+
+impl<T: Serialize, const R: usize, const C: usize> Serialize for Matrix<T, R, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const R: usize, const C: usize> Deserialize<'de>
+    for Matrix<T, R, C>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_array(deserializer).map(Matrix)
+    }
+}
+
+// serde only implements arrays up to 32 elements, a tuple works for any N
+fn serialize_array<T: Serialize, S: Serializer, const N: usize>(
+    array: &[T; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut tuple = serializer.serialize_tuple(N)?;
+    for element in array {
+        tuple.serialize_element(element)?;
+    }
+    tuple.end()
+}
+
+fn deserialize_array<'de, T: Deserialize<'de>, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[T; N], D::Error> {
+    struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+        type Value = [T; N];
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "an array of {} elements", N)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<[T; N], A::Error> {
+            let mut elements = Vec::with_capacity(N);
+            for i in 0..N {
+                match seq.next_element()? {
+                    Some(element) => elements.push(element),
+                    None => return Err(de::Error::invalid_length(i, &self)),
+                }
+            }
+
+            // A tuple deserializer stops after N, but a plain sequence could keep going
+            if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                return Err(de::Error::invalid_length(N + 1, &self));
+            }
+
+            Ok(elements.try_into().unwrap_or_else(|_| unreachable!()))
+        }
+    }
+
+    deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+}
+
+// The splines, ArcLengthTable and VisibilityMask go through their constructors so a file
+// can't create one that panics later
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "CatmullRom")]
+struct CatmullRomData<const N: usize> {
+    points: Vec<Vector<f32, N>>,
+    closed: bool,
+}
+
+impl<'de, const N: usize> Deserialize<'de> for CatmullRom<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = CatmullRomData::deserialize(deserializer)?;
+        let count = data.points.len();
+        let spline = match data.closed {
+            true => CatmullRom::new_closed(data.points),
+            false => CatmullRom::new(data.points),
+        };
+
+        spline.ok_or_else(|| de::Error::custom(format!("too few points for CatmullRom: {}", count)))
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "BSpline")]
+struct BSplineData<const N: usize> {
+    control_points: Vec<Vector<f32, N>>,
+}
+
+impl<'de, const N: usize> Deserialize<'de> for BSpline<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = BSplineData::deserialize(deserializer)?;
+        let count = data.control_points.len();
+
+        BSpline::new(data.control_points)
+            .ok_or_else(|| de::Error::custom(format!("too few points for BSpline: {}", count)))
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "ArcLengthTable")]
+struct ArcLengthTableData {
+    lengths: Vec<f32>,
+}
+
+impl<'de> Deserialize<'de> for ArcLengthTable {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = ArcLengthTableData::deserialize(deserializer)?;
+
+        ArcLengthTable::from_lengths(data.lengths)
+            .ok_or_else(|| de::Error::custom("invalid ArcLengthTable lengths"))
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "VisibilityMask")]
+struct VisibilityMaskData {
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl<'de> Deserialize<'de> for VisibilityMask {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = VisibilityMaskData::deserialize(deserializer)?;
+        let len = data.len;
+
+        VisibilityMask::from_words(data.bits, len).ok_or_else(|| {
+            de::Error::custom(format!("invalid VisibilityMask bits for {} objects", len))
+        })
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod serde_tests {
+    use crate::color::{Color, Rgba8};
+    use crate::math::*;
+
+    #[test]
+    fn test_vectors_are_arrays() {
+        let v = Vector3::new(1.0, 2.5, -3.0);
+        assert_eq!(serde_json::to_string(&v).unwrap(), "[1.0,2.5,-3.0]");
+        assert_eq!(
+            serde_json::from_str::<Vector3>("[1.0,2.5,-3.0]").unwrap(),
+            v
+        );
+        assert_eq!(ron::to_string(&v).unwrap(), "(1.0,2.5,-3.0)");
+        assert_eq!(ron::from_str::<Vector3>("(1.0, 2.5, -3.0)").unwrap(), v);
+
+        let v = Vector2i::new(-1, 2);
+        assert_eq!(
+            serde_json::from_str::<Vector2i>(&serde_json::to_string(&v).unwrap()).unwrap(),
+            v
+        );
+
+        assert!(serde_json::from_str::<Vector3>("[1.0,2.0]").is_err());
+        assert!(serde_json::from_str::<Vector3>("[1.0,2.0,3.0,4.0]").is_err());
+    }
+
+    #[test]
+    fn test_matrices_are_column_arrays() {
+        let m = Matrix2::from_array([[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!(serde_json::to_string(&m).unwrap(), "[[1.0,2.0],[3.0,4.0]]");
+
+        let m = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0));
+        let json = serde_json::to_string(&m).unwrap();
+        assert!(json.ends_with("[1.0,2.0,3.0,1.0]]"));
+        assert_eq!(serde_json::from_str::<Matrix4>(&json).unwrap(), m);
+        assert_eq!(
+            ron::from_str::<Matrix4>(&ron::to_string(&m).unwrap()).unwrap(),
+            m
+        );
+
+        let m = Matrix3d::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        assert_eq!(
+            serde_json::from_str::<Matrix3d>(&serde_json::to_string(&m).unwrap()).unwrap(),
+            m
+        );
+    }
+
+    #[test]
+    fn test_derived_types_round_trip() {
+        let transform = Transform::new(
+            Vector3::new(1.0, 2.0, 3.0),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), Deg(30.0)),
+            Vector3::splat(2.0),
+        );
+        let json = serde_json::to_string(&transform).unwrap();
+        assert!(json.starts_with("{\"translation\":[1.0,2.0,3.0],\"rotation\":{\"x\":"));
+        assert_eq!(serde_json::from_str::<Transform>(&json).unwrap(), transform);
+
+        let ron = ron::to_string(&transform).unwrap();
+        assert_eq!(ron::from_str::<Transform>(&ron).unwrap(), transform);
+
+        let aabb = Aabb::new(Vector3::splat(-1.0), Vector3::splat(1.0));
+        assert_eq!(
+            serde_json::from_str::<Aabb>(&serde_json::to_string(&aabb).unwrap()).unwrap(),
+            aabb
+        );
+
+        assert_eq!(ron::to_string(&Deg(90.0)).unwrap(), "(90.0)");
+        assert_eq!(serde_json::to_string(&EulerOrder::YXZ).unwrap(), "\"YXZ\"");
+    }
+
+    #[test]
+    fn test_splines_are_validated() {
+        let points = vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(1.0, 1.0),
+        ];
+        let spline = CatmullRom::new_closed(points).unwrap();
+        let json = serde_json::to_string(&spline).unwrap();
+        assert_eq!(
+            json,
+            "{\"points\":[[0.0,0.0],[1.0,0.0],[1.0,1.0]],\"closed\":true}"
+        );
+        assert_eq!(
+            serde_json::from_str::<CatmullRom<2>>(&json).unwrap(),
+            spline
+        );
+
+        let too_few = "{\"points\":[[0.0,0.0],[1.0,0.0]],\"closed\":true}";
+        assert!(serde_json::from_str::<CatmullRom<2>>(too_few).is_err());
+        assert!(serde_json::from_str::<BSpline<2>>("{\"control_points\":[[0.0,0.0]]}").is_err());
+    }
+
+    #[test]
+    fn test_colors() {
+        let color = Color::new(1.0, 0.5, 0.25, 0.75);
+        assert_eq!(
+            serde_json::to_string(&color).unwrap(),
+            "[1.0,0.5,0.25,0.75]"
+        );
+        assert_eq!(
+            serde_json::from_str::<Color>("[1.0,0.5,0.25,0.75]").unwrap(),
+            color
+        );
+        assert_eq!(
+            ron::from_str::<Color>(&ron::to_string(&color).unwrap()).unwrap(),
+            color
+        );
+        assert!(serde_json::from_str::<Color>("[1.0,0.5,0.25]").is_err());
+
+        let rgba = Rgba8::new(255, 128, 0, 255);
+        assert_eq!(
+            serde_json::from_str::<Rgba8>(&serde_json::to_string(&rgba).unwrap()).unwrap(),
+            rgba
+        );
+        assert_eq!(
+            ron::from_str::<Rgba8>(&ron::to_string(&rgba).unwrap()).unwrap(),
+            rgba
+        );
+    }
+
+    #[test]
+    fn test_tables_and_masks_are_validated() {
+        let line = CubicBezier::new(
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(3.0, 0.0),
+        );
+        let table = ArcLengthTable::new(&line, 4);
+        let json = serde_json::to_string(&table).unwrap();
+        assert_eq!(json, "{\"lengths\":[0.0,0.75,1.5,2.25,3.0]}");
+        assert_eq!(
+            serde_json::from_str::<ArcLengthTable>(&json).unwrap(),
+            table
+        );
+        assert!(serde_json::from_str::<ArcLengthTable>("{\"lengths\":[]}").is_err());
+        assert!(serde_json::from_str::<ArcLengthTable>("{\"lengths\":[0.0,2.0,1.0]}").is_err());
+
+        let frustum = Frustum::from_matrix(&Matrix4::perspective(Deg(90.0), 1.0, 1.0, 100.0));
+        let spheres: Vec<Sphere> = (0..70)
+            .map(|i| Sphere::new(Vector3::new(0.0, 0.0, -(i as f32)), 0.1))
+            .collect();
+        let mask = frustum.cull_spheres(&spheres);
+        let ron = ron::to_string(&mask).unwrap();
+        assert_eq!(ron::from_str::<VisibilityMask>(&ron).unwrap(), mask);
+        assert_eq!(
+            serde_json::from_str::<VisibilityMask>(&serde_json::to_string(&mask).unwrap()).unwrap(),
+            mask
+        );
+        assert!(serde_json::from_str::<VisibilityMask>("{\"bits\":[1],\"len\":70}").is_err());
+        assert!(serde_json::from_str::<VisibilityMask>("{\"bits\":[4],\"len\":2}").is_err());
+    }
+}
@@ -0,0 +1,423 @@
+// SIMD versions of the hot Matrix4 operations for f32: matrix * matrix, matrix * vector
+// and transforming whole slices of points. The path is picked at compile time:
+// AVX when the target enables it (e.g. RUSTFLAGS="-C target-cpu=native"), SSE2 on
+// any other x86/x86_64 target with SSE2, and the plain generic code everywhere else.
+//
+// Every path does the same multiplies and adds in the same order without FMA,
+// so they give bit-identical results.
+
+use crate::math::{Matrix4, Vector3, Vector4};
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "avx"
+))]
+use avx as imp;
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(target_feature = "avx")
+))]
+use sse2 as imp;
+
+#[cfg(not(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+)))]
+use scalar as imp;
+
+// SAFETY (for all the functions below): imp is only the sse2 or avx module when the
+// target was compiled with that feature enabled
+#[inline]
+#[allow(unused_unsafe)]
+pub(crate) fn mul_matrix4(a: &Matrix4, b: &Matrix4) -> Matrix4 {
+    unsafe { imp::mul_matrix4(a, b) }
+}
+
+#[inline]
+#[allow(unused_unsafe)]
+pub(crate) fn mul_matrix4_vector(m: &Matrix4, v: Vector4) -> Vector4 {
+    unsafe { imp::mul_matrix4_vector(m, v) }
+}
+
+#[inline]
+#[allow(unused_unsafe)]
+pub(crate) fn transform_points(m: &Matrix4, points: &mut [Vector3]) {
+    unsafe { imp::transform_points(m, points) }
+}
+
+#[inline]
+#[allow(unused_unsafe)]
+pub(crate) fn transform_vectors(m: &Matrix4, vectors: &mut [Vector4]) {
+    unsafe { imp::transform_vectors(m, vectors) }
+}
+
+#[allow(dead_code)]
+pub(crate) mod scalar {
+    use crate::math::{matrix_mul_matrix, matrix_mul_vector, Matrix4, Vector3, Vector4};
+
+    #[inline]
+    pub fn mul_matrix4(a: &Matrix4, b: &Matrix4) -> Matrix4 {
+        matrix_mul_matrix(a, b)
+    }
+
+    #[inline]
+    pub fn mul_matrix4_vector(m: &Matrix4, v: Vector4) -> Vector4 {
+        matrix_mul_vector(m, v)
+    }
+
+    #[inline]
+    pub fn transform_points(m: &Matrix4, points: &mut [Vector3]) {
+        for point in points {
+            *point = matrix_mul_vector(m, point.extend(1.0)).xyz();
+        }
+    }
+
+    #[inline]
+    pub fn transform_vectors(m: &Matrix4, vectors: &mut [Vector4]) {
+        for vector in vectors {
+            *vector = matrix_mul_vector(m, *vector);
+        }
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[allow(dead_code)]
+pub(crate) mod sse2 {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    use crate::math::{Matrix4, Vector3, Vector4};
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn load_columns(m: &Matrix4) -> [__m128; 4] {
+        let ptr = m.as_ptr();
+        [
+            _mm_loadu_ps(ptr),
+            _mm_loadu_ps(ptr.add(4)),
+            _mm_loadu_ps(ptr.add(8)),
+            _mm_loadu_ps(ptr.add(12)),
+        ]
+    }
+
+    // c0 * x + c1 * y + c2 * z + c3 * w
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn combine(c: &[__m128; 4], x: f32, y: f32, z: f32, w: f32) -> __m128 {
+        let sum = _mm_mul_ps(c[0], _mm_set1_ps(x));
+        let sum = _mm_add_ps(sum, _mm_mul_ps(c[1], _mm_set1_ps(y)));
+        let sum = _mm_add_ps(sum, _mm_mul_ps(c[2], _mm_set1_ps(z)));
+        _mm_add_ps(sum, _mm_mul_ps(c[3], _mm_set1_ps(w)))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn store(v: __m128) -> Vector4 {
+        let mut out = [0.0; 4];
+        _mm_storeu_ps(out.as_mut_ptr(), v);
+        Vector4::from_array(out)
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn mul_matrix4(a: &Matrix4, b: &Matrix4) -> Matrix4 {
+        let columns = load_columns(a);
+        let mut out = Matrix4::identity();
+
+        for i in 0..4 {
+            let v = b[i];
+            out[i] = store(combine(&columns, v.x, v.y, v.z, v.w));
+        }
+
+        out
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn mul_matrix4_vector(m: &Matrix4, v: Vector4) -> Vector4 {
+        store(combine(&load_columns(m), v.x, v.y, v.z, v.w))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn transform_points(m: &Matrix4, points: &mut [Vector3]) {
+        let columns = load_columns(m);
+
+        for point in points {
+            let v = store(combine(&columns, point.x, point.y, point.z, 1.0));
+            *point = v.xyz();
+        }
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn transform_vectors(m: &Matrix4, vectors: &mut [Vector4]) {
+        let columns = load_columns(m);
+
+        for vector in vectors {
+            *vector = store(combine(&columns, vector.x, vector.y, vector.z, vector.w));
+        }
+    }
+}
+
+// Same as sse2 but works on two columns / points at once, the low 128 bits hold the
+// first one and the high 128 bits the second
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[allow(dead_code)]
+pub(crate) mod avx {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    use crate::math::{Matrix4, Vector3, Vector4};
+
+    // Every column of m repeated in both halves
+    #[inline]
+    #[target_feature(enable = "avx")]
+    unsafe fn load_columns(m: &Matrix4) -> [__m256; 4] {
+        let ptr = m.as_ptr();
+        [
+            _mm256_broadcast_ps(&_mm_loadu_ps(ptr)),
+            _mm256_broadcast_ps(&_mm_loadu_ps(ptr.add(4))),
+            _mm256_broadcast_ps(&_mm_loadu_ps(ptr.add(8))),
+            _mm256_broadcast_ps(&_mm_loadu_ps(ptr.add(12))),
+        ]
+    }
+
+    // c0 * x + c1 * y + c2 * z + c3 * w for two vectors a and b, all components
+    // of a and b are already spread over their halves
+    #[inline]
+    #[target_feature(enable = "avx")]
+    unsafe fn combine(c: &[__m256; 4], ab: [__m256; 4]) -> __m256 {
+        let sum = _mm256_mul_ps(c[0], ab[0]);
+        let sum = _mm256_add_ps(sum, _mm256_mul_ps(c[1], ab[1]));
+        let sum = _mm256_add_ps(sum, _mm256_mul_ps(c[2], ab[2]));
+        _mm256_add_ps(sum, _mm256_mul_ps(c[3], ab[3]))
+    }
+
+    // Splits 8 consecutive floats (two vectors) into their x, y, z and w halves
+    #[inline]
+    #[target_feature(enable = "avx")]
+    unsafe fn spread(ptr: *const f32) -> [__m256; 4] {
+        let ab = _mm256_loadu_ps(ptr);
+        [
+            _mm256_permute_ps(ab, 0b00_00_00_00),
+            _mm256_permute_ps(ab, 0b01_01_01_01),
+            _mm256_permute_ps(ab, 0b10_10_10_10),
+            _mm256_permute_ps(ab, 0b11_11_11_11),
+        ]
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx")]
+    unsafe fn spread_points(a: Vector3, b: Vector3) -> [__m256; 4] {
+        [
+            _mm256_setr_ps(a.x, a.x, a.x, a.x, b.x, b.x, b.x, b.x),
+            _mm256_setr_ps(a.y, a.y, a.y, a.y, b.y, b.y, b.y, b.y),
+            _mm256_setr_ps(a.z, a.z, a.z, a.z, b.z, b.z, b.z, b.z),
+            _mm256_set1_ps(1.0),
+        ]
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx")]
+    unsafe fn store(v: __m256) -> [f32; 8] {
+        let mut out = [0.0; 8];
+        _mm256_storeu_ps(out.as_mut_ptr(), v);
+        out
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx")]
+    pub unsafe fn mul_matrix4(a: &Matrix4, b: &Matrix4) -> Matrix4 {
+        let columns = load_columns(a);
+        let ptr = b.as_ptr();
+
+        let mut out = Matrix4::identity();
+        let out_ptr = &mut out as *mut Matrix4 as *mut f32;
+
+        _mm256_storeu_ps(out_ptr, combine(&columns, spread(ptr)));
+        _mm256_storeu_ps(out_ptr.add(8), combine(&columns, spread(ptr.add(8))));
+
+        out
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx")]
+    pub unsafe fn mul_matrix4_vector(m: &Matrix4, v: Vector4) -> Vector4 {
+        super::sse2::mul_matrix4_vector(m, v)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx")]
+    pub unsafe fn transform_points(m: &Matrix4, points: &mut [Vector3]) {
+        let columns = load_columns(m);
+        let mut pairs = points.chunks_exact_mut(2);
+
+        for pair in &mut pairs {
+            let out = store(combine(&columns, spread_points(pair[0], pair[1])));
+            pair[0] = Vector3::new(out[0], out[1], out[2]);
+            pair[1] = Vector3::new(out[4], out[5], out[6]);
+        }
+
+        super::sse2::transform_points(m, pairs.into_remainder());
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx")]
+    pub unsafe fn transform_vectors(m: &Matrix4, vectors: &mut [Vector4]) {
+        let columns = load_columns(m);
+        let mut pairs = vectors.chunks_exact_mut(2);
+
+        for pair in &mut pairs {
+            let ptr = pair.as_mut_ptr() as *mut f32;
+            _mm256_storeu_ps(ptr, combine(&columns, spread(ptr)));
+        }
+
+        super::sse2::transform_vectors(m, pairs.into_remainder());
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(all(test, any(target_arch = "x86", target_arch = "x86_64")))]
+mod simd_tests {
+    use crate::math::simd::{avx, scalar, sse2};
+    use crate::math::*;
+
+    // Irregular values so any mixed up lane or column shows up
+    fn sample_matrices() -> Vec<Matrix4> {
+        let model = Matrix4::create_translation(Matrix4::identity(), Vector3::new(1.5, -2.25, 3.0))
+            * Matrix4::from_matrix3(Matrix3::from_matrix4(
+                Quaternion::from_axis_angle(Vector3::new(0.0, 0.6, 0.8), Rad(0.7)).to_matrix4(),
+            ));
+
+        vec![
+            Matrix4::identity(),
+            model,
+            Matrix4::perspective(Rad(1.1), 1.6, 0.1, 250.0) * model,
+            Matrix4::from_array(std::array::from_fn(|c| {
+                std::array::from_fn(|r| (c * 4 + r) as f32 * 0.37 - 2.9)
+            })),
+        ]
+    }
+
+    fn sample_points() -> Vec<Vector3> {
+        (0..7)
+            .map(|i| {
+                let i = i as f32;
+                Vector3::new(i * 1.3 - 4.0, -i * 0.7, i * i * 0.11 + 0.5)
+            })
+            .collect()
+    }
+
+    fn sample_vectors() -> Vec<Vector4> {
+        sample_points()
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| p.extend(i as f32 * 0.25 - 0.5))
+            .collect()
+    }
+
+    // Runs f on every path the running cpu supports, with the scalar result as reference
+    fn check_paths<T: PartialEq + std::fmt::Debug>(
+        scalar: impl Fn() -> T,
+        sse2: impl Fn() -> T,
+        avx: impl Fn() -> T,
+    ) {
+        let expected = scalar();
+
+        if is_x86_feature_detected!("sse2") {
+            assert_eq!(sse2(), expected);
+        }
+
+        if is_x86_feature_detected!("avx") {
+            assert_eq!(avx(), expected);
+        }
+    }
+
+    #[test]
+    fn test_mul_matrix4_paths_agree() {
+        for a in sample_matrices() {
+            for b in sample_matrices() {
+                check_paths(
+                    || scalar::mul_matrix4(&a, &b),
+                    || unsafe { sse2::mul_matrix4(&a, &b) },
+                    || unsafe { avx::mul_matrix4(&a, &b) },
+                );
+
+                assert_eq!(a * b, scalar::mul_matrix4(&a, &b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_matrix4_vector_paths_agree() {
+        for m in sample_matrices() {
+            for v in sample_vectors() {
+                check_paths(
+                    || scalar::mul_matrix4_vector(&m, v),
+                    || unsafe { sse2::mul_matrix4_vector(&m, v) },
+                    || unsafe { avx::mul_matrix4_vector(&m, v) },
+                );
+
+                assert_eq!(m * v, scalar::mul_matrix4_vector(&m, v));
+            }
+        }
+    }
+
+    #[test]
+    fn test_transform_points_paths_agree() {
+        // 7 points so the avx path also runs its leftover point
+        for m in sample_matrices() {
+            let run = |f: &dyn Fn(&mut [Vector3])| {
+                let mut points = sample_points();
+                f(&mut points);
+                points
+            };
+
+            check_paths(
+                || run(&|p| scalar::transform_points(&m, p)),
+                || run(&|p| unsafe { sse2::transform_points(&m, p) }),
+                || run(&|p| unsafe { avx::transform_points(&m, p) }),
+            );
+
+            let expected: Vec<Vector3> = sample_points()
+                .into_iter()
+                .map(|p| (m * p.extend(1.0)).xyz())
+                .collect();
+            assert_eq!(run(&|p| m.transform_points(p)), expected);
+        }
+    }
+
+    #[test]
+    fn test_transform_vectors_paths_agree() {
+        for m in sample_matrices() {
+            let run = |f: &dyn Fn(&mut [Vector4])| {
+                let mut vectors = sample_vectors();
+                f(&mut vectors);
+                vectors
+            };
+
+            check_paths(
+                || run(&|v| scalar::transform_vectors(&m, v)),
+                || run(&|v| unsafe { sse2::transform_vectors(&m, v) }),
+                || run(&|v| unsafe { avx::transform_vectors(&m, v) }),
+            );
+
+            let expected: Vec<Vector4> = sample_vectors().into_iter().map(|v| m * v).collect();
+            assert_eq!(run(&|v| m.transform_vectors(v)), expected);
+        }
+    }
+
+    #[test]
+    fn test_empty_slices() {
+        let m = sample_matrices()[1];
+        m.transform_points(&mut []);
+        m.transform_vectors(&mut []);
+    }
+}
@@ -0,0 +1,490 @@
+use std::ops::{Mul, MulAssign};
+
+use crate::math::{Matrix3, Matrix4, Matrix4d, Quaternion, Vector3, Vector3d};
+
+// Translation, rotation and scale, applied as scale first, then rotation, then translation.
+// Same as the matrix translation * rotation * scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+    pub translation: Vector3,
+    pub rotation: Quaternion,
+    pub scale: Vector3,
+}
+
+impl Transform {
+    // @params: rotation must be normalized
+    pub fn new(translation: Vector3, rotation: Quaternion, scale: Vector3) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    pub fn identity() -> Transform {
+        Transform::new(Vector3::zero(), Quaternion::identity(), Vector3::splat(1.0))
+    }
+
+    pub fn from_translation(translation: Vector3) -> Transform {
+        Transform {
+            translation,
+            ..Transform::identity()
+        }
+    }
+
+    pub fn from_rotation(rotation: Quaternion) -> Transform {
+        Transform {
+            rotation,
+            ..Transform::identity()
+        }
+    }
+
+    pub fn from_scale(scale: Vector3) -> Transform {
+        Transform {
+            scale,
+            ..Transform::identity()
+        }
+    }
+
+    pub fn to_matrix(self) -> Matrix4 {
+        let rotation = self.rotation.to_matrix3();
+
+        let mut result = Matrix4::from_matrix3(Matrix3::from_columns(
+            rotation.x * self.scale.x,
+            rotation.y * self.scale.y,
+            rotation.z * self.scale.z,
+        ));
+        result.w = self.translation.extend(1.0);
+
+        result
+    }
+
+    pub fn transform_point(self, point: Vector3) -> Vector3 {
+        self.translation + self.rotation * (self.scale * point)
+    }
+
+    // Directions ignore the translation
+    pub fn transform_vector(self, vector: Vector3) -> Vector3 {
+        self.rotation * (self.scale * vector)
+    }
+
+    // Undoes this transform, returns None if any scale component is zero.
+    // Only exact for uniform scale, a rotated non-uniform scale can't be expressed as TRS.
+    pub fn inverse(self) -> Option<Transform> {
+        if self.scale.x == 0.0 || self.scale.y == 0.0 || self.scale.z == 0.0 {
+            return None;
+        }
+
+        let scale = Vector3::splat(1.0) / self.scale;
+        let rotation = self.rotation.conjugate();
+        let translation = -(scale * (rotation * self.translation));
+
+        Some(Transform::new(translation, rotation, scale))
+    }
+
+    // Translation and scale are interpolated linearly, rotation with slerp.
+    // t = 0 gives self, t = 1 gives other.
+    pub fn lerp(self, other: Transform, t: f32) -> Transform {
+        Transform::new(
+            self.translation.lerp(other.translation, t),
+            self.rotation.slerp(other.rotation, t),
+            self.scale.lerp(other.scale, t),
+        )
+    }
+}
+
+// Transform with a double precision translation, for worlds too large for f32 positions.
+// Rebase it on the camera with relative_to or model_view before anything goes to the GPU,
+// so objects near the camera keep full f32 precision however far they are from the origin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorldTransform {
+    pub translation: Vector3d,
+    pub rotation: Quaternion,
+    pub scale: Vector3,
+}
+
+impl WorldTransform {
+    // @params: rotation must be normalized
+    pub fn new(translation: Vector3d, rotation: Quaternion, scale: Vector3) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    pub fn identity() -> WorldTransform {
+        WorldTransform::new(
+            Vector3d::zero(),
+            Quaternion::identity(),
+            Vector3::splat(1.0),
+        )
+    }
+
+    pub fn from_translation(translation: Vector3d) -> WorldTransform {
+        WorldTransform {
+            translation,
+            ..WorldTransform::identity()
+        }
+    }
+
+    pub fn translate(&mut self, offset: Vector3d) {
+        self.translation += offset;
+    }
+
+    // The same transform with origin moved to zero. The subtraction happens in f64,
+    // only the (small) difference is rounded to f32.
+    pub fn relative_to(self, origin: Vector3d) -> Transform {
+        Transform::new(
+            (self.translation - origin).cast(),
+            self.rotation,
+            self.scale,
+        )
+    }
+
+    // Full precision model matrix, for CPU side work like picking far from the origin
+    pub fn to_matrix(self) -> Matrix4d {
+        let mut result: Matrix4d = Transform::new(Vector3::zero(), self.rotation, self.scale)
+            .to_matrix()
+            .convert();
+        result.w = self.translation.extend(1.0);
+
+        result
+    }
+
+    pub fn transform_point(self, point: Vector3d) -> Vector3d {
+        self.translation + self.transform_vector(point.cast()).convert()
+    }
+
+    // Directions ignore the translation and stay f32
+    pub fn transform_vector(self, vector: Vector3) -> Vector3 {
+        self.rotation * (self.scale * vector)
+    }
+
+    // view * model with the camera at the origin, ready for shader::uniform_matrix_4fv.
+    // @params: view_rotation is the camera's view matrix without its translation,
+    // like Matrix4::camera_relative_look_at_rh
+    pub fn model_view(self, camera_position: Vector3d, view_rotation: &Matrix4) -> Matrix4 {
+        *view_rotation * self.relative_to(camera_position).to_matrix()
+    }
+}
+
+impl Default for WorldTransform {
+    fn default() -> Self {
+        WorldTransform::identity()
+    }
+}
+
+impl From<Transform> for WorldTransform {
+    fn from(transform: Transform) -> WorldTransform {
+        WorldTransform::new(
+            transform.translation.convert(),
+            transform.rotation,
+            transform.scale,
+        )
+    }
+}
+
+// parent * child with the child in the parent's local space, like Transform products.
+// Only exact when the parent's scale is uniform.
+impl Mul<Transform> for WorldTransform {
+    type Output = WorldTransform;
+
+    fn mul(self, rhs: Transform) -> WorldTransform {
+        WorldTransform::new(
+            self.transform_point(rhs.translation.convert()),
+            (self.rotation * rhs.rotation).normalized(),
+            self.scale * rhs.scale,
+        )
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::identity()
+    }
+}
+
+// parent * child gives the child's transform in the parent's space, like Matrix4 products.
+// Only exact when the parent's scale is uniform, same as inverse.
+impl Mul for Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: Transform) -> Transform {
+        Transform::new(
+            self.transform_point(rhs.translation),
+            (self.rotation * rhs.rotation).normalized(),
+            self.scale * rhs.scale,
+        )
+    }
+}
+
+impl MulAssign for Transform {
+    fn mul_assign(&mut self, rhs: Transform) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<Vector3> for Transform {
+    type Output = Vector3;
+
+    fn mul(self, rhs: Vector3) -> Vector3 {
+        self.transform_point(rhs)
+    }
+}
+
+impl From<Transform> for Matrix4 {
+    fn from(transform: Transform) -> Matrix4 {
+        transform.to_matrix()
+    }
+}
+
+impl Matrix4 {
+    // View matrix for camera relative rendering: the camera sits at the origin and only the
+    // direction to the target is kept, computed in f64 so large coordinates don't jitter.
+    // Use with WorldTransform::model_view.
+    pub fn camera_relative_look_at_rh(eye: Vector3d, target: Vector3d, up: Vector3) -> Matrix4 {
+        Matrix4::look_at_rh(Vector3::zero(), (target - eye).cast(), up)
+    }
+
+    pub fn camera_relative_look_at_lh(eye: Vector3d, target: Vector3d, up: Vector3) -> Matrix4 {
+        Matrix4::look_at_lh(Vector3::zero(), (target - eye).cast(), up)
+    }
+
+    // Splits an affine matrix into translation, rotation and scale. A mirrored matrix
+    // (negative determinant) gets a negative x scale.
+    // Returns None if the matrix has a zero scale. Shear is lost.
+    pub fn decompose(&self) -> Option<Transform> {
+        let basis = Matrix3::from_matrix4(*self);
+
+        let mut scale = Vector3::new(basis.x.length(), basis.y.length(), basis.z.length());
+
+        if scale.x == 0.0 || scale.y == 0.0 || scale.z == 0.0 {
+            return None;
+        }
+
+        if basis.determinant() < 0.0 {
+            scale.x = -scale.x;
+        }
+
+        let rotation =
+            Matrix3::from_columns(basis.x / scale.x, basis.y / scale.y, basis.z / scale.z);
+
+        Some(Transform::new(
+            self.w.xyz(),
+            Quaternion::from_matrix3(rotation).normalized(),
+            scale,
+        ))
+    }
+}
+
+// ###########################  TESTS  ####################################################################
+#[cfg(test)]
+mod transform_tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::assert_abs_diff_eq;
+    use crate::math::*;
+
+    fn sample_transform() -> Transform {
+        Transform::new(
+            Vector3::new(1.0, -2.0, 3.5),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 0.6, 0.8), Rad(0.9)),
+            Vector3::new(2.0, 0.5, 3.0),
+        )
+    }
+
+    #[test]
+    fn test_identity_is_identity_matrix() {
+        assert_eq!(Transform::identity().to_matrix(), Matrix4::identity());
+        assert_eq!(Transform::default(), Transform::identity());
+    }
+
+    #[test]
+    fn test_to_matrix_matches_trs_product() {
+        let t = sample_transform();
+
+        let expected = Matrix4::create_translation(Matrix4::identity(), t.translation)
+            * t.rotation.to_matrix4()
+            * Matrix4::from_matrix3(Matrix3::new(
+                t.scale.x, 0.0, 0.0, 0.0, t.scale.y, 0.0, 0.0, 0.0, t.scale.z,
+            ));
+
+        assert_abs_diff_eq!(t.to_matrix(), expected, 1e-4);
+
+        let point = Vector3::new(0.3, -1.0, 2.0);
+        assert_abs_diff_eq!(t * point, (t.to_matrix() * point.extend(1.0)).xyz(), 1e-4);
+    }
+
+    #[test]
+    fn test_decompose_round_trip() {
+        let t = sample_transform();
+        let decomposed = t.to_matrix().decompose().unwrap();
+
+        assert_abs_diff_eq!(decomposed.translation, t.translation, 1e-4);
+        assert_abs_diff_eq!(decomposed.rotation, t.rotation, 1e-4);
+        assert_abs_diff_eq!(decomposed.scale, t.scale, 1e-4);
+    }
+
+    #[test]
+    fn test_decompose_mirrored() {
+        let t = Transform::new(
+            Vector3::zero(),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), Rad(FRAC_PI_2)),
+            Vector3::new(-1.0, 2.0, 2.0),
+        );
+
+        let decomposed = t.to_matrix().decompose().unwrap();
+
+        assert_abs_diff_eq!(decomposed.to_matrix(), t.to_matrix(), 1e-4);
+        assert!(decomposed.scale.x < 0.0);
+    }
+
+    #[test]
+    fn test_decompose_zero_scale() {
+        let m = Transform::from_scale(Vector3::new(1.0, 0.0, 1.0)).to_matrix();
+        assert_eq!(m.decompose(), None);
+    }
+
+    #[test]
+    fn test_composition_matches_matrices() {
+        // Uniform parent scale so the result is exact
+        let parent = Transform::new(
+            Vector3::new(5.0, 0.0, -1.0),
+            Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), Rad(0.4)),
+            Vector3::splat(2.0),
+        );
+        let child = sample_transform();
+
+        assert_abs_diff_eq!(
+            (parent * child).to_matrix(),
+            parent.to_matrix() * child.to_matrix(),
+            1e-4
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        let t = Transform::new(
+            Vector3::new(1.0, 2.0, 3.0),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), Rad(1.2)),
+            Vector3::splat(4.0),
+        );
+
+        let inverse = t.inverse().unwrap();
+        assert_abs_diff_eq!((t * inverse).to_matrix(), Matrix4::identity(), 1e-4);
+        assert_abs_diff_eq!((inverse * t).to_matrix(), Matrix4::identity(), 1e-4);
+
+        assert_eq!(
+            Transform::from_scale(Vector3::new(1.0, 1.0, 0.0)).inverse(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_lerp() {
+        let a = Transform::from_translation(Vector3::new(0.0, 0.0, 0.0));
+        let b = Transform::new(
+            Vector3::new(10.0, 0.0, 0.0),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), Rad(FRAC_PI_2)),
+            Vector3::splat(3.0),
+        );
+
+        let half = a.lerp(b, 0.5);
+
+        assert_abs_diff_eq!(half.translation, Vector3::new(5.0, 0.0, 0.0), 1e-4);
+        assert_abs_diff_eq!(half.scale, Vector3::splat(2.0), 1e-4);
+        assert_abs_diff_eq!(
+            half.rotation,
+            Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), Rad(FRAC_PI_2 / 2.0)),
+            1e-4
+        );
+
+        assert_eq!(a.lerp(b, 0.0), a);
+    }
+
+    #[test]
+    fn test_world_transform_matches_f64_matrices() {
+        let eye = Vector3d::new(5000.0, 20.0, -300.0);
+        let target = Vector3d::new(5010.0, 18.0, -320.0);
+        let up = Vector3d::new(0.0, 1.0, 0.0);
+
+        let world = WorldTransform::new(
+            Vector3d::new(5004.0, 19.5, -310.0),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 0.6, 0.8), Rad(0.9)),
+            Vector3::new(2.0, 0.5, 3.0),
+        );
+
+        let expected = (Matrix4d::look_at_rh(eye, target, up) * world.to_matrix()).cast::<f32>();
+        let view = Matrix4::camera_relative_look_at_rh(eye, target, up.cast());
+
+        assert_abs_diff_eq!(world.model_view(eye, &view), expected, 1e-4);
+
+        let expected = (Matrix4d::look_at_lh(eye, target, up) * world.to_matrix()).cast::<f32>();
+        let view = Matrix4::camera_relative_look_at_lh(eye, target, up.cast());
+        assert_abs_diff_eq!(world.model_view(eye, &view), expected, 1e-4);
+    }
+
+    #[test]
+    fn test_world_transform_keeps_precision_far_from_origin() {
+        // f32 can't tell these positions apart, the spacing of f32 at 1e8 is 8
+        let camera = Vector3d::splat(1e8);
+        let world = WorldTransform::from_translation(camera + Vector3d::new(0.25, 0.5, -1.0));
+        assert_eq!(camera.cast::<f32>(), world.translation.cast::<f32>());
+
+        let view = Matrix4::camera_relative_look_at_rh(
+            camera,
+            camera + Vector3d::new(0.0, 0.0, -1.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        );
+        let eye_space = world.model_view(camera, &view) * Vector4::new(0.0, 0.0, 0.0, 1.0);
+
+        assert_eq!(eye_space, Vector4::new(0.25, 0.5, -1.0, 1.0));
+        assert_eq!(
+            world.relative_to(camera).translation,
+            Vector3::new(0.25, 0.5, -1.0)
+        );
+    }
+
+    #[test]
+    fn test_world_transform_points_and_hierarchy() {
+        let mut world = WorldTransform::from(sample_transform());
+        assert_eq!(world.translation, Vector3d::new(1.0, -2.0, 3.5));
+
+        let point = Vector3::new(0.5, 1.0, -2.0);
+        let expected = sample_transform().transform_point(point);
+        assert!((world.transform_point(point.convert()).cast() - expected).length() < 1e-5);
+
+        world.translate(Vector3d::new(1e9, 0.0, 0.0));
+        assert_eq!(world.translation, Vector3d::new(1e9 + 1.0, -2.0, 3.5));
+
+        // Children compose like Transform, with the parent's translation kept in f64
+        let child = Transform::from_translation(Vector3::new(0.0, 1.0, 0.0));
+        let combined = world * child;
+        let local = sample_transform() * child;
+        assert!(
+            (combined.relative_to(world.translation).translation
+                - (local.translation - sample_transform().translation))
+                .length()
+                < 1e-5
+        );
+        assert_abs_diff_eq!(combined.rotation, local.rotation, 1e-4);
+
+        assert_eq!(WorldTransform::default(), WorldTransform::identity());
+        assert_eq!(WorldTransform::identity().to_matrix(), Matrix4d::identity());
+    }
+
+    #[test]
+    fn test_f64_translation() {
+        let translation = Matrix4d::from_translation(Vector3d::new(1e10, 2.0, 0.5));
+        let moved = translation * Vector4d::new(0.125, 0.0, 0.0, 1.0);
+
+        assert_eq!(moved, Vector4d::new(1e10 + 0.125, 2.0, 0.5, 1.0));
+        assert_eq!(
+            Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0)),
+            Matrix4::create_translation(Matrix4::identity(), Vector3::new(1.0, 2.0, 3.0))
+        );
+    }
+}
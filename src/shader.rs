@@ -2,6 +2,7 @@ use std::ffi::CString;
 
 use gl::types::*;
 
+use crate::color::Color;
 use crate::math::Matrix4;
 
 pub enum ShaderType {
@@ -218,6 +219,11 @@ pub fn uniform4f(location: i32, r: f32, g: f32, b: f32, a: f32) {
     unsafe { gl::Uniform4f(location, r, g, b, a) }
 }
 
-pub fn uniform_matrix_4fv(location: i32, data: Matrix4) {
-    unsafe { gl::UniformMatrix4fv(location, 1, gl::FALSE, data.to_ptr()) }
+// vec4 uniform, the color stays linear so the shader can light with it
+pub fn uniform_color(location: i32, color: Color) {
+    unsafe { gl::Uniform4fv(location, 1, color.as_ptr()) }
+}
+
+pub fn uniform_matrix_4fv(location: i32, data: &Matrix4) {
+    unsafe { gl::UniformMatrix4fv(location, 1, gl::FALSE, data.as_ptr()) }
 }
@@ -2,11 +2,13 @@ use std::ffi::c_void;
 
 use gl::types::*;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureType {
     Texture2D = gl::TEXTURE_2D as isize,
     Texture3D = gl::TEXTURE_3D as isize,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureOption {
     //GL_TEXTURE_WRAP_S, GL_TEXTURE_WRAP_T, or GL_TEXTURE_WRAP_R.
     TextureWrapR = gl::TEXTURE_WRAP_R as isize,
@@ -16,6 +18,7 @@ pub enum TextureOption {
     MagFilter = gl::TEXTURE_MAG_FILTER as isize,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureOptionValue {
     Repeat = gl::REPEAT as isize,
     MirroredRepeat = gl::MIRRORED_REPEAT as isize,
@@ -25,6 +28,7 @@ pub enum TextureOptionValue {
     Linear = gl::LINEAR as isize,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InternalFormat {
     RG = gl::RG as isize,
     RGB = gl::RGB as isize,